@@ -43,6 +43,11 @@ struct AddCustomExtensionSheet: View {
         return !ext.isEmpty && FileTypeRegistry.shared.fileType(for: ext) == nil
     }
 
+    private var coveredExtensions: [String] {
+        guard selectedLanguage != "plaintext" else { return [] }
+        return FileTypeRegistry.shared.extensions(forLanguage: selectedLanguage)
+    }
+
     private var isValid: Bool {
         let trimmedDisplay = displayName.trimmingCharacters(in: .whitespaces)
         guard !trimmedDisplay.isEmpty else { return false }
@@ -160,9 +165,17 @@ struct AddCustomExtensionSheet: View {
                 } header: {
                     Text("Syntax Highlighting")
                 } footer: {
-                    Text("Choose which language to use for syntax highlighting")
-                        .font(.caption)
-                        .foregroundStyle(.secondary)
+                    VStack(alignment: .leading, spacing: 6) {
+                        Text("Choose which language to use for syntax highlighting")
+                            .font(.caption)
+                            .foregroundStyle(.secondary)
+
+                        if !coveredExtensions.isEmpty {
+                            Text("Already covers: \(coveredExtensions.map { ".\($0)" }.joined(separator: ", "))")
+                                .font(.caption)
+                                .foregroundStyle(.secondary)
+                        }
+                    }
                 }
             }
             .formStyle(.grouped)
@@ -138,7 +138,7 @@ struct StatusView: View {
                 Spacer(minLength: 20)
 
                 HStack {
-                    Text("v\(Bundle.main.infoDictionary?["CFBundleShortVersionString"] as? String ?? "_x.x")")
+                    Text("v\(BuildInfo.current().version)")
                         .font(.caption)
                         .foregroundStyle(.secondary)
 
@@ -34,14 +34,19 @@ final class HighlightService: NSObject, NSXPCListenerDelegate, HighlightServiceP
         let signpostID = signposter.makeSignpostID()
         let interval = shouldLog ? signposter.beginInterval("highlight.total", id: signpostID) : nil
 
+        let budget = HighlightBudget()
         let html = highlighter.highlight(
             code: code,
             language: language,
             showLineNumbers: showLineNumbers,
             shouldCancel: { [weak cancellationRegistry] in
                 cancellationRegistry?.isCancelled(requestId) ?? false
-            }
+            },
+            budget: budget
         )
+        if budget.isDegraded {
+            os_log("Highlight budget exhausted for %{public}@; rest rendered plain", log: log, type: .info, requestId)
+        }
 
         if let interval {
             signposter.endInterval("highlight.total", interval)
@@ -69,13 +74,18 @@ final class HighlightService: NSObject, NSXPCListenerDelegate, HighlightServiceP
             return
         }
 
+        let budget = HighlightBudget()
         let tokens = highlighter.extractTokens(
             code: code,
             language: language,
             shouldCancel: { [weak cancellationRegistry] in
                 cancellationRegistry?.isCancelled(requestId) ?? false
-            }
+            },
+            budget: budget
         )
+        if budget.isDegraded {
+            os_log("Token budget exhausted for %{public}@; rest left plain", log: log, type: .info, requestId)
+        }
 
         if cancellationRegistry.isCancelled(requestId) || tokens == nil {
             let error = NSError(domain: "com.stianlars1.dotViewer.HighlightCancelled", code: 1)
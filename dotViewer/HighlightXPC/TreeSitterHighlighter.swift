@@ -6,28 +6,63 @@ final class TreeSitterHighlighter {
         let id: String
         let language: OpaquePointer
         let query: OpaquePointer
+        /// Whether the highlight query captures escape sequences itself.
+        let hasEscapeCaptures: Bool
     }
 
-    private let configs: [String: LanguageConfig]
+    /// Grammars are compiled on first use: a Quick Look process usually previews one language, so
+    /// compiling every highlight query up front spent startup time and memory on unused grammars.
+    private let configLock = NSLock()
+    private var configs: [String: LanguageConfig] = [:]
+    private var unavailableGrammars: Set<String> = []
+    /// Gives escape sequences inside string literals their own `tok-escape` spans.
+    private let highlightStringEscapes: Bool
 
-    init() {
-        configs = Self.loadConfigs()
+    init(highlightStringEscapes: Bool = true) {
+        self.highlightStringEscapes = highlightStringEscapes
+    }
+
+    private func config(for key: String) -> LanguageConfig? {
+        let id = Self.grammarAliases[key] ?? key
+        configLock.lock()
+        defer { configLock.unlock() }
+
+        if let config = configs[id] {
+            return config
+        }
+        guard !unavailableGrammars.contains(id), let language = Self.grammarLanguage(for: id) else {
+            return nil
+        }
+        guard let queryString = Self.loadQuery(named: id),
+              let query = Self.compileQuery(queryString, language: language)
+        else {
+            unavailableGrammars.insert(id)
+            return nil
+        }
+        let config = LanguageConfig(id: id, language: language, query: query, hasEscapeCaptures: Self.capturesEscapes(query))
+        configs[id] = config
+        return config
     }
 
     func extractTokens(
         code: String,
         language: String,
-        shouldCancel: (() -> Bool)? = nil
+        shouldCancel: (() -> Bool)? = nil,
+        budget: HighlightBudget? = nil
     ) -> [HighlightToken]? {
         if shouldCancel?() == true { return nil }
-        let loweredLanguage = language.lowercased()
+        let loweredLanguage = Self.grammarKey(for: language)
 
         guard let data = code.data(using: .utf8) else { return [] }
 
-        guard let config = configs[loweredLanguage] else {
+        guard let config = config(for: loweredLanguage) else {
             guard loweredLanguage != "plaintext", !loweredLanguage.isEmpty else { return [] }
-            let captures = Self.fallbackHighlightCaptures(data: data, shouldCancel: shouldCancel)
+            var captures = Self.fallbackCaptures(language: loweredLanguage, data: data, shouldCancel: shouldCancel, budget: budget)
             if shouldCancel?() == true { return nil }
+            if highlightStringEscapes {
+                captures = Self.splittingStringEscapes(captures, data: data, language: loweredLanguage, scanBytes: true)
+            }
+            captures = Self.snappingToGraphemes(captures, code: code)
             return captures.map { HighlightToken(s: $0.start, e: $0.end, c: Self.mapCaptureToClass($0.name).dropTokPrefix()) }
         }
 
@@ -59,6 +94,7 @@ final class TreeSitterHighlighter {
         while ts_query_cursor_next_capture(cursor, &match, &captureIndex) {
             captureCount += 1
             if captureCount % 200 == 0, shouldCancel?() == true { return nil }
+            if captureCount % 200 == 0, budget?.isExhausted() == true { break }
             guard let capturePtr = match.captures else { continue }
             let captureBuffer = UnsafeBufferPointer(start: capturePtr, count: Int(match.capture_count))
             let capture = captureBuffer[Int(captureIndex)]
@@ -74,10 +110,12 @@ final class TreeSitterHighlighter {
             captures.append(Capture(start: start, end: end, name: name))
         }
 
+        var usedFallback = false
         if captures.isEmpty {
-            let fallback = Self.fallbackHighlightCaptures(data: data, shouldCancel: shouldCancel)
+            let fallback = Self.fallbackCaptures(language: loweredLanguage, data: data, shouldCancel: shouldCancel, budget: budget)
             if shouldCancel?() == true { return nil }
             captures = fallback
+            usedFallback = true
         }
 
         captures.sort { lhs, rhs in
@@ -87,6 +125,16 @@ final class TreeSitterHighlighter {
 
         if shouldCancel?() == true { return nil }
 
+        if highlightStringEscapes {
+            captures = Self.splittingStringEscapes(
+                captures,
+                data: data,
+                language: loweredLanguage,
+                scanBytes: usedFallback || !config.hasEscapeCaptures
+            )
+        }
+        captures = Self.snappingToGraphemes(captures, code: code)
+
         // Deduplicate overlapping captures (same logic as renderHighlighted)
         var tokens: [HighlightToken] = []
         var currentIndex = 0
@@ -103,13 +151,14 @@ final class TreeSitterHighlighter {
         code: String,
         language: String,
         showLineNumbers: Bool,
-        shouldCancel: (() -> Bool)? = nil
+        shouldCancel: (() -> Bool)? = nil,
+        budget: HighlightBudget? = nil
     ) -> String? {
         if shouldCancel?() == true {
             return nil
         }
-        let loweredLanguage = language.lowercased()
-        guard let config = configs[loweredLanguage] else {
+        let loweredLanguage = Self.grammarKey(for: language)
+        guard let config = config(for: loweredLanguage) else {
             // We support a small set of tree-sitter grammars. For everything else, we still apply a
             // lightweight heuristic highlighter so "supported file types" don't silently turn into
             // plain text.
@@ -119,11 +168,16 @@ final class TreeSitterHighlighter {
             guard let data = code.data(using: .utf8) else {
                 return Self.renderPlain(code: code, showLineNumbers: showLineNumbers)
             }
-            let captures = Self.fallbackHighlightCaptures(data: data, shouldCancel: shouldCancel)
+            var captures = Self.fallbackCaptures(language: loweredLanguage, data: data, shouldCancel: shouldCancel, budget: budget)
             if shouldCancel?() == true {
                 return nil
             }
-            return Self.renderHighlighted(data: data, captures: captures, showLineNumbers: showLineNumbers)
+            if highlightStringEscapes {
+                captures = Self.splittingStringEscapes(captures, data: data, language: loweredLanguage, scanBytes: true)
+            }
+            captures = Self.snappingToGraphemes(captures, code: code)
+            let html = Self.renderHighlighted(data: data, captures: captures, showLineNumbers: showLineNumbers)
+            return budget?.isDegraded == true ? html + HighlightBudget.degradedMarker : html
         }
 
         guard let data = code.data(using: .utf8) else {
@@ -178,6 +232,10 @@ final class TreeSitterHighlighter {
             if captureCount % 200 == 0, shouldCancel?() == true {
                 return nil
             }
+            // Out of time: keep what we have; the rest of the file renders as plain text.
+            if captureCount % 200 == 0, budget?.isExhausted() == true {
+                break
+            }
             guard let capturePtr = match.captures else { continue }
             let captureBuffer = UnsafeBufferPointer(start: capturePtr, count: Int(match.capture_count))
             let capture = captureBuffer[Int(captureIndex)]
@@ -201,12 +259,14 @@ final class TreeSitterHighlighter {
             captures.append(Capture(start: start, end: end, name: name))
         }
 
+        var usedFallback = false
         if captures.isEmpty {
-            let fallback = Self.fallbackHighlightCaptures(data: data, shouldCancel: shouldCancel)
+            let fallback = Self.fallbackCaptures(language: loweredLanguage, data: data, shouldCancel: shouldCancel, budget: budget)
             if shouldCancel?() == true {
                 return nil
             }
             captures = fallback
+            usedFallback = true
         }
 
         captures.sort { lhs, rhs in
@@ -219,7 +279,17 @@ final class TreeSitterHighlighter {
         if shouldCancel?() == true {
             return nil
         }
-        return Self.renderHighlighted(data: data, captures: captures, showLineNumbers: showLineNumbers)
+        if highlightStringEscapes {
+            captures = Self.splittingStringEscapes(
+                captures,
+                data: data,
+                language: loweredLanguage,
+                scanBytes: usedFallback || !config.hasEscapeCaptures
+            )
+        }
+        captures = Self.snappingToGraphemes(captures, code: code)
+        let html = Self.renderHighlighted(data: data, captures: captures, showLineNumbers: showLineNumbers)
+        return budget?.isDegraded == true ? html + HighlightBudget.degradedMarker : html
     }
 }
 
@@ -241,84 +311,83 @@ private extension TreeSitterHighlighter {
         "union", "using", "var", "virtual", "void", "where", "while", "with", "yield"
     ]
 
-    private static func loadConfigs() -> [String: LanguageConfig] {
-        var configs: [String: LanguageConfig] = [:]
-
-        let languages: [(String, OpaquePointer?)] = [
-            ("swift", tree_sitter_swift()),
-            ("python", tree_sitter_python()),
-            ("javascript", tree_sitter_javascript()),
-            ("typescript", tree_sitter_typescript()),
-            ("tsx", tree_sitter_tsx()),
-            ("json", tree_sitter_json()),
-            ("yaml", tree_sitter_yaml()),
-            ("markdown", tree_sitter_markdown()),
-            ("bash", tree_sitter_bash()),
-            ("html", tree_sitter_html()),
-            ("css", tree_sitter_css()),
-            ("xml", tree_sitter_xml()),
-            ("ini", tree_sitter_ini()),
-            ("toml", tree_sitter_toml()),
-            ("c", tree_sitter_c()),
-            ("cpp", tree_sitter_cpp()),
-            ("go", tree_sitter_go()),
-            ("rust", tree_sitter_rust()),
-            ("java", tree_sitter_java()),
-            ("ruby", tree_sitter_ruby()),
-            ("php", tree_sitter_php()),
-            ("lua", tree_sitter_lua()),
-            ("sql", tree_sitter_sql()),
-            ("dockerfile", tree_sitter_dockerfile()),
-            ("r", tree_sitter_r()),
-            ("scala", tree_sitter_scala()),
-            ("kotlin", tree_sitter_kotlin()),
-            ("c_sharp", tree_sitter_c_sharp()),
-            ("perl", tree_sitter_perl()),
-            ("dart", tree_sitter_dart()),
-            ("elixir", tree_sitter_elixir()),
-            ("haskell", tree_sitter_haskell()),
-            ("ocaml", tree_sitter_ocaml()),
-            ("zig", tree_sitter_zig()),
-            ("make", tree_sitter_make()),
-            ("cmake", tree_sitter_cmake()),
-            ("graphql", tree_sitter_graphql()),
-            ("hcl", tree_sitter_hcl()),
-            ("nix", tree_sitter_nix()),
-            ("scss", tree_sitter_scss()),
-            ("regex", tree_sitter_regex()),
-            ("julia", tree_sitter_julia()),
-            ("erlang", tree_sitter_erlang()),
-            ("clojure", tree_sitter_clojure()),
-            ("vim", tree_sitter_vim()),
-            ("fortran", tree_sitter_fortran()),
-            ("pascal", tree_sitter_pascal()),
-            ("d", tree_sitter_d()),
-            ("gleam", tree_sitter_gleam()),
-            ("objc", tree_sitter_objc()),
-            ("wat", tree_sitter_wat()),
-            ("fish", tree_sitter_fish()),
-            ("awk", tree_sitter_awk()),
-        ]
-
-        for (id, language) in languages {
-            guard let language,
-                  let queryString = loadQuery(named: id),
-                  let query = compileQuery(queryString, language: language)
-            else {
-                continue
-            }
-            configs[id] = LanguageConfig(id: id, language: language, query: query)
-        }
+    // Aliases so picker IDs resolve to compiled grammars
+    private static let grammarAliases: [String: String] = [
+        "csharp": "c_sharp",
+        "makefile": "make",
+        "objectivec": "objc",
+    ]
 
-        // Aliases so picker IDs resolve to compiled grammars
-        if let cSharp = configs["c_sharp"] { configs["csharp"] = cSharp }
-        if let make = configs["make"] { configs["makefile"] = make }
-        if let objc = configs["objc"] { configs["objectivec"] = objc }
+    private static func grammarLanguage(for id: String) -> OpaquePointer? {
+        switch id {
+        case "swift": return tree_sitter_swift()
+        case "python": return tree_sitter_python()
+        case "javascript": return tree_sitter_javascript()
+        case "typescript": return tree_sitter_typescript()
+        case "tsx": return tree_sitter_tsx()
+        case "json": return tree_sitter_json()
+        case "yaml": return tree_sitter_yaml()
+        case "markdown": return tree_sitter_markdown()
+        case "bash": return tree_sitter_bash()
+        case "html": return tree_sitter_html()
+        case "css": return tree_sitter_css()
+        case "xml": return tree_sitter_xml()
+        case "ini": return tree_sitter_ini()
+        case "toml": return tree_sitter_toml()
+        case "c": return tree_sitter_c()
+        case "cpp": return tree_sitter_cpp()
+        case "go": return tree_sitter_go()
+        case "rust": return tree_sitter_rust()
+        case "java": return tree_sitter_java()
+        case "ruby": return tree_sitter_ruby()
+        case "php": return tree_sitter_php()
+        case "lua": return tree_sitter_lua()
+        case "sql": return tree_sitter_sql()
+        case "dockerfile": return tree_sitter_dockerfile()
+        case "r": return tree_sitter_r()
+        case "scala": return tree_sitter_scala()
+        case "kotlin": return tree_sitter_kotlin()
+        case "c_sharp": return tree_sitter_c_sharp()
+        case "perl": return tree_sitter_perl()
+        case "dart": return tree_sitter_dart()
+        case "elixir": return tree_sitter_elixir()
+        case "haskell": return tree_sitter_haskell()
+        case "ocaml": return tree_sitter_ocaml()
+        case "zig": return tree_sitter_zig()
+        case "make": return tree_sitter_make()
+        case "cmake": return tree_sitter_cmake()
+        case "graphql": return tree_sitter_graphql()
+        case "hcl": return tree_sitter_hcl()
+        case "nix": return tree_sitter_nix()
+        case "scss": return tree_sitter_scss()
+        case "regex": return tree_sitter_regex()
+        case "julia": return tree_sitter_julia()
+        case "erlang": return tree_sitter_erlang()
+        case "clojure": return tree_sitter_clojure()
+        case "vim": return tree_sitter_vim()
+        case "fortran": return tree_sitter_fortran()
+        case "pascal": return tree_sitter_pascal()
+        case "d": return tree_sitter_d()
+        case "gleam": return tree_sitter_gleam()
+        case "objc": return tree_sitter_objc()
+        case "wat": return tree_sitter_wat()
+        case "fish": return tree_sitter_fish()
+        case "awk": return tree_sitter_awk()
+        default: return nil
+        }
+    }
 
-        return configs
+    /// Heuristic spans for when no grammar applies or the grammar captured nothing. INI-style
+    /// files get a line-based highlighter that knows sections, keys and comment lines.
+    static func fallbackCaptures(language: String, data: Data, shouldCancel: (() -> Bool)?, budget: HighlightBudget?) -> [Capture] {
+        guard language == "ini" else {
+            return fallbackHighlightCaptures(data: data, shouldCancel: shouldCancel, budget: budget)
+        }
+        return IniHighlighter.tokens(in: String(decoding: data, as: UTF8.self))
+            .map { Capture(start: $0.s, end: $0.e, name: $0.c) }
     }
 
-    static func fallbackHighlightCaptures(data: Data, shouldCancel: (() -> Bool)?) -> [Capture] {
+    static func fallbackHighlightCaptures(data: Data, shouldCancel: (() -> Bool)?, budget: HighlightBudget? = nil) -> [Capture] {
         // A fast, UTF-8 byte based fallback highlighter. This is intentionally simple:
         // - comments (//, #, --, /* */)
         // - strings ("...", '...', `...`, and basic triple quotes)
@@ -342,6 +411,9 @@ private extension TreeSitterHighlighter {
             if i % 4096 == 0, shouldCancel?() == true {
                 return []
             }
+            if i % 4096 == 0, budget?.isExhausted() == true {
+                return captures
+            }
 
             let b = bytes[i]
 
@@ -537,6 +609,48 @@ private extension TreeSitterHighlighter {
         return captures
     }
 
+    /// Escape captures nest inside string captures, and the renderer skips nested captures, so
+    /// strings are split around their escapes before rendering. `scanBytes` is false when the
+    /// grammar has its own escape query, so strings it left whole (raw literals) stay whole.
+    static func splittingStringEscapes(_ captures: [Capture], data: Data, language: String, scanBytes: Bool) -> [Capture] {
+        let spans = captures.map { HighlightToken(s: $0.start, e: $0.end, c: $0.name) }
+        return StringEscapeSplitter.split(
+            spans,
+            bytes: Array(data),
+            // Only real string/regex literals; markdown code spans also map to tok-string.
+            isString: { mapCaptureToClass($0) == "tok-string" && !$0.lowercased().contains("literal") },
+            isEscape: { mapCaptureToClass($0) == "tok-escape" },
+            scanBytes: scanBytes,
+            rawQuotes: StringEscapeSplitter.rawQuotes(forLanguage: language)
+        ).map { Capture(start: $0.s, end: $0.e, name: $0.c) }
+    }
+
+    /// Whether any capture name in the compiled query maps to `tok-escape`. Reading the names rather
+    /// than the .scm text keeps comments and uncaptured nodes like `escape_sequence` from counting.
+    static func capturesEscapes(_ query: OpaquePointer) -> Bool {
+        (0..<ts_query_capture_count(query)).contains { id in
+            var length: UInt32 = 0
+            guard let namePtr = ts_query_capture_name_for_id(query, id, &length) else { return false }
+            let nameBytes = UnsafeBufferPointer(start: UnsafeRawPointer(namePtr).assumingMemoryBound(to: UInt8.self), count: Int(length))
+            return mapCaptureToClass(String(decoding: nameBytes, as: UTF8.self)) == "tok-escape"
+        }
+    }
+
+    /// Tree-sitter node edges are bytes and can land between a base character and its combining
+    /// marks, so captures are aligned to grapheme clusters before they become spans.
+    static func snappingToGraphemes(_ captures: [Capture], code: String) -> [Capture] {
+        guard !code.utf8.allSatisfy({ $0 < 0x80 }) else { return captures }
+        let spans = captures.map { HighlightToken(s: $0.start, e: $0.end, c: $0.name) }
+        return GraphemeBoundaries.snap(spans, to: GraphemeBoundaries.offsets(in: code))
+            .map { Capture(start: $0.s, end: $0.e, name: $0.c) }
+    }
+
+    /// Grammar key for a language id, resolving short names such as `js`, `yml`, or `c++`.
+    static func grammarKey(for language: String) -> String {
+        let normalized = language.trimmingCharacters(in: .whitespacesAndNewlines).lowercased()
+        return FileTypeRegistry.resolveLanguageAlias(normalized) ?? normalized
+    }
+
     static func loadQuery(named name: String) -> String? {
         guard let url = Bundle.main.url(forResource: name, withExtension: "scm"),
               let data = try? Data(contentsOf: url)
@@ -616,7 +730,7 @@ private extension TreeSitterHighlighter {
     }
 
     static func escapeHTML(_ string: String) -> String {
-        var escaped = string
+        var escaped = TextLineUtilities.visibleControlCharacters(string)
         escaped = escaped.replacingOccurrences(of: "&", with: "&amp;")
         escaped = escaped.replacingOccurrences(of: "<", with: "&lt;")
         escaped = escaped.replacingOccurrences(of: ">", with: "&gt;")
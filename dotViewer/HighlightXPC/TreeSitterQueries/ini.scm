@@ -1,7 +1,7 @@
 (section_name
   (text) @type) ; consistency with toml
 
-(comment) @comment @spell
+(comment) @comment
 
 [
   "["
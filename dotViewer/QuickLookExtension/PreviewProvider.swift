@@ -168,6 +168,7 @@ final class PreviewProvider: QLPreviewProvider, QLPreviewingController {
         )
 
         if cacheEnabled, let cached = await PreviewCache.shared.load(key: cacheKey, ttlSeconds: cacheTTL) {
+            let themeName = ThemeDirectiveDetector.theme(in: cached.rawText) ?? SharedSettings.shared.selectedTheme
             let info = PreviewInfo(
                 title: url.lastPathComponent,
                 language: languageName.isEmpty ? "Text" : languageName,
@@ -195,7 +196,7 @@ final class PreviewProvider: QLPreviewProvider, QLPreviewingController {
                 markdownShowInlineImages: SharedSettings.shared.markdownShowInlineImages,
                 markdownCustomCSS: SharedSettings.shared.markdownCustomCSS,
                 markdownCustomCSSOverride: SharedSettings.shared.markdownCustomCSSOverride,
-                themeName: SharedSettings.shared.selectedTheme,
+                themeName: themeName,
                 showUnknownTextWarning: showUnknownTextWarning,
                 showBinaryWarning: showBinaryWarning,
                 systemIsDark: systemIsDark,
@@ -208,7 +209,7 @@ final class PreviewProvider: QLPreviewProvider, QLPreviewingController {
                 sourceDirectory: url.deletingLastPathComponent().path
             )
 
-            let palette = ThemePalette.palette(for: SharedSettings.shared.selectedTheme, systemIsDark: systemIsDark)
+            let palette = ThemePalette.palette(for: themeName, systemIsDark: systemIsDark)
             let html = PreviewHTMLBuilder.buildHTML(info: info, palette: palette)
             routeLogger.log("HTML built (cache) for \(url.lastPathComponent, privacy: .public)")
             return makeHTMLReply(
@@ -251,6 +252,7 @@ final class PreviewProvider: QLPreviewProvider, QLPreviewingController {
             routeLogger.log("Request cancelled after read for \(url.lastPathComponent, privacy: .public)")
         }
 
+        let themeName = ThemeDirectiveDetector.theme(in: fileInfo.text) ?? SharedSettings.shared.selectedTheme
         var effectiveLanguageId = languageId
         var effectiveLanguageName = languageName
 
@@ -281,11 +283,31 @@ final class PreviewProvider: QLPreviewProvider, QLPreviewingController {
             rawHTML = specialHTML
         } else if isMarkdown && !useMarkdownHighlight {
             rawHTML = PlainTextRenderer.render(code: fileInfo.text, showLineNumbers: showLineNumbers)
+        } else if shouldAttemptHighlight, effectiveLanguageId == "diff" {
+            var payloadLines: [String]?
+            if let payloadLanguage = DiffRenderer.payloadLanguage(in: fileInfo.text) {
+                let payloadResult = await HighlightXPCClient.shared.highlight(
+                    code: DiffRenderer.payloadText(in: fileInfo.text),
+                    language: payloadLanguage,
+                    theme: themeName,
+                    showLineNumbers: false,
+                    requestId: requestId,
+                    timeout: 3.0
+                )
+                if case .success(let html) = payloadResult {
+                    payloadLines = DiffRenderer.highlightedLines(fromCodeHTML: html)
+                }
+            }
+            rawHTML = DiffRenderer.renderHTML(
+                text: fileInfo.text,
+                highlightedPayload: payloadLines,
+                showLineNumbers: showLineNumbers
+            )
         } else if shouldAttemptHighlight {
             let highlightResult = await HighlightXPCClient.shared.highlight(
                 code: fileInfo.text,
                 language: effectiveLanguageId,
-                theme: SharedSettings.shared.selectedTheme,
+                theme: themeName,
                 showLineNumbers: showLineNumbers,
                 requestId: requestId,
                 timeout: 3.0
@@ -349,7 +371,7 @@ final class PreviewProvider: QLPreviewProvider, QLPreviewingController {
             markdownShowInlineImages: SharedSettings.shared.markdownShowInlineImages,
             markdownCustomCSS: SharedSettings.shared.markdownCustomCSS,
             markdownCustomCSSOverride: SharedSettings.shared.markdownCustomCSSOverride,
-            themeName: SharedSettings.shared.selectedTheme,
+            themeName: themeName,
             showUnknownTextWarning: showUnknownTextWarning,
             showBinaryWarning: showBinaryWarning,
             systemIsDark: systemIsDark,
@@ -362,12 +384,13 @@ final class PreviewProvider: QLPreviewProvider, QLPreviewingController {
             sourceDirectory: url.deletingLastPathComponent().path
         )
 
-        let palette = ThemePalette.palette(for: SharedSettings.shared.selectedTheme, systemIsDark: systemIsDark)
+        let palette = ThemePalette.palette(for: themeName, systemIsDark: systemIsDark)
         let html = PreviewHTMLBuilder.buildHTML(info: info, palette: palette)
         routeLogger.log("HTML built for \(url.lastPathComponent, privacy: .public)")
 
         let isCurrentRequest = await PreviewRequestCoordinator.shared.isCurrent(requestId)
-        let shouldCache = cacheEnabled && !info.isSensitive && isCurrentRequest
+        let cachePolicy = PreviewCachePolicy.recommended(byteCount: fileInfo.fileSizeBytes, lineCount: fileInfo.lineCount)
+        let shouldCache = cacheEnabled && !info.isSensitive && isCurrentRequest && cachePolicy != .noCache
         if shouldCache {
             let entry = PreviewCacheEntry(
                 createdAt: Date(),
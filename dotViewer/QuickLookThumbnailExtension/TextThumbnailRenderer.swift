@@ -166,19 +166,34 @@ enum TextThumbnailRenderer {
         url: URL,
         maxBytes: Int,
         maxLines: Int,
-        encoding: String.Encoding
+        encoding: String.Encoding,
+        collapseBlankLines: Bool = false
     ) throws -> TextThumbnailSnippet {
         let handle = try FileHandle(forReadingFrom: url)
         let data = handle.readData(ofLength: maxBytes)
         try? handle.close()
         let text = decodeString(data: data, encoding: encoding)
-        return makeSnippet(text: text, maxLines: maxLines, truncatedByBytes: data.count >= maxBytes)
+        return makeSnippet(
+            text: text,
+            maxLines: maxLines,
+            truncatedByBytes: data.count >= maxBytes,
+            collapseBlankLines: collapseBlankLines
+        )
     }
 
-    static func makeSnippet(text: String, maxLines: Int, truncatedByBytes: Bool) -> TextThumbnailSnippet {
+    /// With `collapseBlankLines`, runs of blank lines take a single row so the thumbnail shows more
+    /// code; `lineCount` still reports the file's real line count.
+    static func makeSnippet(
+        text: String,
+        maxLines: Int,
+        truncatedByBytes: Bool,
+        collapseBlankLines: Bool = false
+    ) -> TextThumbnailSnippet {
+        let text = text.unicodeScalars.first == "\u{FEFF}" ? String(text.unicodeScalars.dropFirst()) : text
         let rawLines = TextLineUtilities.lines(forDisplayFrom: text)
-        let truncatedByLines = rawLines.count > maxLines
-        let limitedLines = Array(rawLines.prefix(maxLines))
+        let shownLines = collapseBlankLines ? TextLineUtilities.collapsingBlankRuns(rawLines) : rawLines
+        let truncatedByLines = shownLines.count > maxLines
+        let limitedLines = Array(shownLines.prefix(maxLines))
         let isTruncated = truncatedByLines || truncatedByBytes
 
         var lines = limitedLines
@@ -475,31 +490,9 @@ enum TokenColorMapper {
         return style(for: tokenClass, palette: palette).color
     }
 
-    /// Bold/italic style per token type (thumbnail-specific — CSS handles this separately in previews).
-    private static func styleFlags(for token: TokenType) -> (isBold: Bool, isItalic: Bool) {
-        switch token {
-        case .comment:     return (false, true)
-        case .keyword:     return (true,  false)
-        case .string:      return (false, false)
-        case .number:      return (false, false)
-        case .type:        return (true,  false)
-        case .function:    return (false, false)
-        case .property:    return (false, false)
-        case .constant:    return (false, false)
-        case .identifier:  return (false, false)
-        case .punctuation: return (false, false)
-        case .tag:         return (true,  false)
-        case .attribute:   return (false, true)
-        case .escape:      return (false, false)
-        case .builtin:     return (false, true)
-        case .namespace:   return (false, false)
-        case .parameter:   return (false, false)
-        }
-    }
-
     static func style(for tokenClass: String, palette: ThemePalette) -> TokenStyle {
         if let token = TokenType(rawValue: tokenClass) {
-            let flags = styleFlags(for: token)
+            let flags = token.styleFlags
             return TokenStyle(
                 color: NSColor(hex: palette.hex(for: token)) ?? .labelColor,
                 isBold: flags.isBold,
@@ -91,7 +91,8 @@ final class ThumbnailProvider: QLThumbnailProvider {
                 snippet = TextThumbnailRenderer.makeSnippet(
                     text: conversion.text,
                     maxLines: settings.thumbnailMaxLines,
-                    truncatedByBytes: conversion.isTruncated
+                    truncatedByBytes: conversion.isTruncated,
+                    collapseBlankLines: settings.collapseBlankLines
                 )
             } else {
                 do {
@@ -99,7 +100,8 @@ final class ThumbnailProvider: QLThumbnailProvider {
                         url: url,
                         maxBytes: settings.thumbnailMaxBytes,
                         maxLines: settings.thumbnailMaxLines,
-                        encoding: encoding
+                        encoding: encoding,
+                        collapseBlankLines: settings.collapseBlankLines
                     )
                 } catch {
                     await MainActor.run {
@@ -173,6 +175,7 @@ private struct ThumbnailSettings: Sendable {
     let showHeader: Bool
     let thumbnailMaxBytes: Int
     let thumbnailMaxLines: Int
+    let collapseBlankLines: Bool
 
     static func capture() -> ThumbnailSettings {
         ThumbnailSettings(
@@ -184,7 +187,8 @@ private struct ThumbnailSettings: Sendable {
             selectedTheme: SharedSettings.shared.selectedTheme,
             showHeader: SharedSettings.shared.showFileInfoHeader,
             thumbnailMaxBytes: SharedSettings.shared.thumbnailMaxBytes,
-            thumbnailMaxLines: SharedSettings.shared.thumbnailMaxLines
+            thumbnailMaxLines: SharedSettings.shared.thumbnailMaxLines,
+            collapseBlankLines: SharedSettings.shared.thumbnailCollapseBlankLines
         )
     }
 }
@@ -0,0 +1,125 @@
+import Foundation
+
+public enum ANSIColorMode: String, CaseIterable, Sendable {
+    case trueColor
+    case xterm256
+    case ansi16
+}
+
+/// Renders highlight tokens as ANSI SGR-colored terminal text using a theme palette.
+public enum ANSIRenderer {
+    public static let reset = "\u{1B}[0m"
+
+    /// Colors `code` with `tokens` (byte offsets, as returned by `highlightTokens`). Each line ends
+    /// with a reset so a colored run never bleeds into the next line or the user's prompt; CRLF
+    /// and CR breaks come out as `\n`.
+    /// `truecolor: false` approximates colors with the xterm 256-color palette.
+    public static func render(code: String, tokens: [HighlightToken], palette: ThemePalette, truecolor: Bool) -> String {
+        render(code: code, tokens: tokens, palette: palette, mode: truecolor ? .trueColor : .xterm256)
+    }
+
+    public static func render(code: String, tokens: [HighlightToken], palette: ThemePalette, mode: ANSIColorMode) -> String {
+        let textColor = RGBColor(hex: palette.text)
+        var output = ""
+        var lineIsOpen = false
+
+        for run in HighlightRuns.runs(in: code, tokens: tokens) {
+            let color = run.tokenType.flatMap { RGBColor(hex: palette.hex(for: $0)) } ?? textColor
+            for (index, part) in run.text.split(omittingEmptySubsequences: false, whereSeparator: HighlightRuns.isLineBreak).enumerated() {
+                if index > 0 {
+                    output += reset + "\n"
+                    lineIsOpen = false
+                }
+                guard !part.isEmpty else { continue }
+                if let color {
+                    output += foregroundSequence(for: color, mode: mode)
+                }
+                // Raw ESC or other controls in the file must not reach the terminal.
+                output += TextLineUtilities.visibleControlCharacters(String(part))
+                lineIsOpen = true
+            }
+        }
+        if lineIsOpen || code.isEmpty {
+            output += reset
+        }
+        return output
+    }
+
+    public static func foregroundSequence(for color: RGBColor, truecolor: Bool) -> String {
+        foregroundSequence(for: color, mode: truecolor ? .trueColor : .xterm256)
+    }
+
+    public static func foregroundSequence(for color: RGBColor, mode: ANSIColorMode) -> String {
+        switch mode {
+        case .trueColor:
+            return "\u{1B}[38;2;\(color.red);\(color.green);\(color.blue)m"
+        case .xterm256:
+            return "\u{1B}[38;5;\(xterm256Index(for: color))m"
+        case .ansi16:
+            let index = ansi16Index(for: color)
+            return "\u{1B}[\(index < 8 ? 30 + index : 90 + index - 8)m"
+        }
+    }
+
+    /// Palette index a color snaps to in `mode`, or nil for true color.
+    public static func colorIndex(for color: RGBColor, mode: ANSIColorMode) -> Int? {
+        switch mode {
+        case .trueColor:
+            return nil
+        case .xterm256:
+            return xterm256Index(for: color)
+        case .ansi16:
+            return ansi16Index(for: color)
+        }
+    }
+
+    /// Index into the 16 standard ANSI colors. Plain RGB distance sends muted theme colors to gray,
+    /// so saturated colors instead keep the channels above their midpoint (preserving hue) and use
+    /// the bright variant when the strongest channel is bright.
+    public static func ansi16Index(for color: RGBColor) -> Int {
+        let channels = [Int(color.red), Int(color.green), Int(color.blue)]
+        let high = channels.max() ?? 0
+        let low = channels.min() ?? 0
+
+        if high - low < 48 {
+            let average = channels.reduce(0, +) / 3
+            switch average {
+            case ..<48: return 0
+            case ..<128: return 8
+            case ..<208: return 7
+            default: return 15
+            }
+        }
+
+        let midpoint = (high + low) / 2
+        let bits = (channels[0] > midpoint ? 1 : 0) | (channels[1] > midpoint ? 2 : 0) | (channels[2] > midpoint ? 4 : 0)
+        return bits + (high > 200 ? 8 : 0)
+    }
+
+    /// Nearest xterm-256 index from the 6×6×6 color cube (16–231) or the grayscale ramp (232–255).
+    public static func xterm256Index(for color: RGBColor) -> Int {
+        let levels = [0, 95, 135, 175, 215, 255]
+        func nearestLevel(_ value: UInt8) -> Int {
+            levels.indices.min { abs(levels[$0] - Int(value)) < abs(levels[$1] - Int(value)) } ?? 0
+        }
+        func distance(_ r: Int, _ g: Int, _ b: Int) -> Int {
+            let dr = r - Int(color.red)
+            let dg = g - Int(color.green)
+            let db = b - Int(color.blue)
+            return dr * dr + dg * dg + db * db
+        }
+
+        let r = nearestLevel(color.red)
+        let g = nearestLevel(color.green)
+        let b = nearestLevel(color.blue)
+        let cubeIndex = 16 + 36 * r + 6 * g + b
+        let cubeDistance = distance(levels[r], levels[g], levels[b])
+
+        let average = (Int(color.red) + Int(color.green) + Int(color.blue)) / 3
+        let grayStep = min(max((average - 8 + 5) / 10, 0), 23)
+        let gray = 8 + 10 * grayStep
+        let grayDistance = distance(gray, gray, gray)
+
+        return grayDistance < cubeDistance ? 232 + grayStep : cubeIndex
+    }
+}
@@ -0,0 +1,87 @@
+import Foundation
+
+public enum InvisibleCharacterKind: String, Sendable {
+    /// Embedding, override and isolate controls that can reorder source text ("Trojan Source").
+    case bidiControl
+    case zeroWidth
+}
+
+public struct InvisibleCharacterMatch: Equatable, Sendable {
+    /// 1-based display line number.
+    public let line: Int
+    /// 0-based character column within the line.
+    public let column: Int
+    public let scalar: Unicode.Scalar
+    public let kind: InvisibleCharacterKind
+
+    public init(line: Int, column: Int, scalar: Unicode.Scalar, kind: InvisibleCharacterKind) {
+        self.line = line
+        self.column = column
+        self.scalar = scalar
+        self.kind = kind
+    }
+
+    /// Short label such as `RLO` or `ZWSP`, for warnings and inline markers.
+    public var abbreviation: String {
+        BidiControlDetector.abbreviations[scalar.value] ?? String(format: "U+%04X", scalar.value)
+    }
+}
+
+/// Finds characters that render invisibly but change how source reads: bidi controls and
+/// zero-width characters. A byte-order mark at the very start of the text is not reported.
+public enum BidiControlDetector {
+    static let abbreviations: [UInt32: String] = [
+        0x202A: "LRE", 0x202B: "RLE", 0x202C: "PDF", 0x202D: "LRO", 0x202E: "RLO",
+        0x2066: "LRI", 0x2067: "RLI", 0x2068: "FSI", 0x2069: "PDI",
+        0x200E: "LRM", 0x200F: "RLM", 0x061C: "ALM",
+        0x200B: "ZWSP", 0x200C: "ZWNJ", 0x200D: "ZWJ", 0x2060: "WJ", 0xFEFF: "BOM",
+    ]
+
+    private static let bidiControls: Set<UInt32> = [
+        0x202A, 0x202B, 0x202C, 0x202D, 0x202E, 0x2066, 0x2067, 0x2068, 0x2069, 0x200E, 0x200F, 0x061C,
+    ]
+    private static let zeroWidth: Set<UInt32> = [0x200B, 0x200C, 0x200D, 0x2060, 0xFEFF]
+
+    public static func detect(in text: String) -> [InvisibleCharacterMatch] {
+        var matches: [InvisibleCharacterMatch] = []
+
+        for (index, line) in TextLineUtilities.displayLines(in: text).enumerated() {
+            for (column, character) in line.enumerated() {
+                for scalar in character.unicodeScalars {
+                    let kind: InvisibleCharacterKind
+                    if bidiControls.contains(scalar.value) {
+                        kind = .bidiControl
+                    } else if zeroWidth.contains(scalar.value) {
+                        // ZWJ inside an emoji sequence is legitimate.
+                        if scalar.value == 0x200D, character.unicodeScalars.contains(where: \.properties.isEmojiPresentation) { continue }
+                        if scalar.value == 0xFEFF, index == 0, column == 0 { continue }
+                        kind = .zeroWidth
+                    } else {
+                        continue
+                    }
+                    matches.append(InvisibleCharacterMatch(line: index + 1, column: column, scalar: scalar, kind: kind))
+                }
+            }
+        }
+        return matches
+    }
+
+    public static func containsBidiControls(_ text: String) -> Bool {
+        text.unicodeScalars.contains { bidiControls.contains($0.value) }
+    }
+
+    /// Blocks of strong right-to-left letters: Hebrew through Arabic Extended, their presentation
+    /// forms, and the historic RTL scripts in the supplementary planes.
+    private static let rightToLeftRanges: [ClosedRange<UInt32>] = [
+        0x0590...0x08FF, 0xFB1D...0xFDFF, 0xFE70...0xFEFF, 0x10800...0x10FFF, 0x1E800...0x1EFFF,
+    ]
+
+    /// True when `text` contains Hebrew, Arabic or other strong right-to-left letters, so the host
+    /// can render that line with `dir="auto"` instead of forcing left-to-right.
+    public static func containsRightToLeftText(_ text: String) -> Bool {
+        text.unicodeScalars.contains { scalar in
+            scalar.value >= 0x0590 && scalar.value != 0xFEFF
+                && rightToLeftRanges.contains { $0.contains(scalar.value) }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+import Foundation
+
+/// Rainbow brackets: after normal highlighting, `()`, `[]` and `{}` take a color by nesting depth,
+/// cycling through `depthColors`. Brackets inside strings and comments are left alone; a closer
+/// without a matching opener, and an opener never closed, get `errorColor`.
+public enum BracketPairColorizer {
+    private static let pairs: [Character: Character] = [")": "(", "]": "[", "}": "{"]
+
+    public static func paletted(
+        in code: String,
+        tokens: [HighlightToken],
+        palette: ThemePalette,
+        depthColors: [String],
+        errorColor: String
+    ) -> PalettedRuns {
+        let base = HighlightRuns.paletted(in: code, tokens: tokens, palette: palette)
+        let bracketColors = colors(in: code, tokens: tokens, depthColors: depthColors, errorColor: errorColor)
+        guard !bracketColors.isEmpty else { return base }
+
+        var colors = base.colors
+        var colorIndices: [String: Int] = [:]
+        for (index, hex) in colors.enumerated() where colorIndices[hex] == nil {
+            colorIndices[hex] = index
+        }
+        var result: [PalettedRun] = []
+        var pending = ""
+        var pendingIndex = 0
+        // Characters collect in `pending` until the color changes, so runs are built once.
+        func flush() {
+            guard !pending.isEmpty else { return }
+            result.append(PalettedRun(colorIndex: pendingIndex, text: pending))
+            pending = ""
+        }
+        func append(_ character: Character, colorIndex: Int) {
+            if colorIndex != pendingIndex {
+                flush()
+                pendingIndex = colorIndex
+            }
+            pending.append(character)
+        }
+
+        var offset = 0
+        for run in base.runs {
+            for character in run.text {
+                if let hex = bracketColors[offset]?.uppercased() {
+                    if colorIndices[hex] == nil {
+                        colorIndices[hex] = colors.count
+                        colors.append(hex)
+                    }
+                    append(character, colorIndex: colorIndices[hex] ?? 0)
+                } else {
+                    append(character, colorIndex: run.colorIndex)
+                }
+                offset += character.utf8.count
+            }
+        }
+        flush()
+        return PalettedRuns(background: base.background, colors: colors, runs: result)
+    }
+
+    /// Color for each bracket, keyed by UTF-8 byte offset.
+    public static func colors(
+        in code: String,
+        tokens: [HighlightToken],
+        depthColors: [String],
+        errorColor: String
+    ) -> [Int: String] {
+        var result: [Int: String] = [:]
+        var open: [(character: Character, offset: Int)] = []
+        var offset = 0
+
+        for run in HighlightRuns.runs(in: code, tokens: tokens) {
+            let skip = run.tokenType == .string || run.tokenType == .comment
+            for character in run.text {
+                defer { offset += character.utf8.count }
+                guard !skip else { continue }
+
+                if "([{".contains(character) {
+                    open.append((character, offset))
+                } else if let opener = pairs[character] {
+                    if open.last?.character == opener, let match = open.popLast() {
+                        let color = depthColors.isEmpty ? errorColor : depthColors[open.count % depthColors.count]
+                        result[match.offset] = color
+                        result[offset] = color
+                    } else {
+                        result[offset] = errorColor
+                    }
+                }
+            }
+        }
+        for unclosed in open {
+            result[unclosed.offset] = errorColor
+        }
+        return result
+    }
+}
@@ -0,0 +1,36 @@
+import Foundation
+
+/// Version and capability counts for support logs ("highlighting looks wrong" reports).
+public struct BuildInfo: Equatable, Sendable {
+    public let version: String
+    public let build: String
+    /// Languages offered in the picker, including basic (non-grammar) ones.
+    public let languageCount: Int
+    public let treeSitterGrammarCount: Int
+    public let themeCount: Int
+
+    public init(version: String, build: String, languageCount: Int, treeSitterGrammarCount: Int, themeCount: Int) {
+        self.version = version
+        self.build = build
+        self.languageCount = languageCount
+        self.treeSitterGrammarCount = treeSitterGrammarCount
+        self.themeCount = themeCount
+    }
+
+    /// Reads the version from `bundle`; bundles without one (e.g. test hosts) report "unknown".
+    public static func current(bundle: Bundle = .main) -> BuildInfo {
+        let info = bundle.infoDictionary ?? [:]
+        return BuildInfo(
+            version: info["CFBundleShortVersionString"] as? String ?? "unknown",
+            build: info["CFBundleVersion"] as? String ?? "unknown",
+            languageCount: HighlightLanguage.all.count,
+            treeSitterGrammarCount: HighlightLanguage.all.filter(\.hasTreeSitterGrammar).count,
+            themeCount: ThemePalette.selectableThemes.count
+        )
+    }
+
+    /// One line suitable for logging.
+    public var summary: String {
+        "dotViewer \(version) (\(build)), \(languageCount) languages (\(treeSitterGrammarCount) tree-sitter), \(themeCount) themes"
+    }
+}
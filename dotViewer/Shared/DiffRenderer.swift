@@ -0,0 +1,168 @@
+import Foundation
+
+public enum DiffLineKind: String, Sendable {
+    case fileHeader
+    case hunkHeader
+    case addition
+    case removal
+    case context
+
+    var cssClass: String {
+        switch self {
+        case .fileHeader:
+            return "diff-file"
+        case .hunkHeader:
+            return "diff-hunk"
+        case .addition:
+            return "diff-add"
+        case .removal:
+            return "diff-del"
+        case .context:
+            return "diff-context"
+        }
+    }
+}
+
+/// Renders unified diffs with per-line add/remove tints. The `+`/`-`/space prefix is split off so
+/// the payload can be syntax-highlighted in the language of the patched file.
+public enum DiffRenderer {
+    private static let fileHeaderPrefixes = ["+++ ", "--- ", "diff ", "index ", "new file mode", "deleted file mode"]
+
+    /// Kind of one line read on its own. Inside a hunk a removed `-- comment` looks like a `--- `
+    /// header, so whole diffs go through `kinds(in:)`.
+    public static func kind(of line: String) -> DiffLineKind {
+        if fileHeaderPrefixes.contains(where: { line.hasPrefix($0) }) {
+            return .fileHeader
+        }
+        if line.hasPrefix("@@") {
+            return .hunkHeader
+        }
+        if line.hasPrefix("+") {
+            return .addition
+        }
+        if line.hasPrefix("-") {
+            return .removal
+        }
+        return .context
+    }
+
+    /// Kind of every display line of `text`. After an `@@ -a,b +c,d @@` header, lines are hunk
+    /// content until its line counts run out or a `diff ` line starts the next file, so `--- x` and
+    /// `+++ y` inside a hunk stay a removal and an addition.
+    public static func kinds(in text: String) -> [DiffLineKind] {
+        kinds(of: TextLineUtilities.displayLines(in: text))
+    }
+
+    static func kinds(of lines: [String]) -> [DiffLineKind] {
+        var oldRemaining = 0
+        var newRemaining = 0
+        return lines.map { line -> DiffLineKind in
+            if line.hasPrefix("diff ") {
+                oldRemaining = 0
+                newRemaining = 0
+                return .fileHeader
+            }
+            if oldRemaining > 0 || newRemaining > 0 {
+                switch line.first {
+                case "+"?:
+                    newRemaining -= 1
+                    return .addition
+                case "-"?:
+                    oldRemaining -= 1
+                    return .removal
+                case " "?, nil:
+                    oldRemaining -= 1
+                    newRemaining -= 1
+                    return .context
+                case "\\"?:
+                    // "\ No newline at end of file" belongs to the line before it.
+                    return .context
+                default:
+                    oldRemaining = 0
+                    newRemaining = 0
+                }
+            }
+            let kind = kind(of: line)
+            if kind == .hunkHeader, let counts = hunkLineCounts(line) {
+                (oldRemaining, newRemaining) = counts
+            }
+            return kind
+        }
+    }
+
+    /// Old and new line counts of an `@@ -a,b +c,d @@` header; an omitted count means one line.
+    private static func hunkLineCounts(_ line: String) -> (old: Int, new: Int)? {
+        let fields = line.split(separator: " ")
+        guard fields.count >= 3, fields[0] == "@@", fields[1].hasPrefix("-"), fields[2].hasPrefix("+") else { return nil }
+
+        func count(_ field: Substring) -> Int? {
+            let parts = field.dropFirst().split(separator: ",", omittingEmptySubsequences: false)
+            guard (1...2).contains(parts.count), parts.allSatisfy({ Int($0) != nil }) else { return nil }
+            return parts.count == 2 ? Int(parts[1]) : 1
+        }
+        guard let old = count(fields[1]), let new = count(fields[2]) else { return nil }
+        return (old, new)
+    }
+
+    /// Highlight language of the patched file, taken from the first `+++` path that isn't /dev/null.
+    public static func payloadLanguage(in text: String) -> String? {
+        let lines = TextLineUtilities.displayLines(in: text)
+        for (line, kind) in zip(lines, kinds(of: lines)) where kind == .fileHeader && line.hasPrefix("+++ ") {
+            var path = line.dropFirst(4).split(separator: "\t").first.map(String.init) ?? ""
+            if path == "/dev/null" { continue }
+            if path.hasPrefix("b/") { path.removeFirst(2) }
+            let ext = (path as NSString).pathExtension
+            guard !ext.isEmpty, let language = FileTypeRegistry.shared.highlightLanguage(for: ext) else { continue }
+            return language == "diff" ? nil : language
+        }
+        return nil
+    }
+
+    /// Diff text with prefixes removed and header lines blanked, one payload line per diff line, so
+    /// highlighting it yields lines that align with the original diff.
+    public static func payloadText(in text: String) -> String {
+        let lines = TextLineUtilities.displayLines(in: text)
+        return zip(lines, kinds(of: lines)).map { line, kind in
+            switch kind {
+            case .fileHeader, .hunkHeader:
+                return ""
+            case .addition, .removal, .context:
+                return String(line.dropFirst())
+            }
+        }.joined(separator: "\n")
+    }
+
+    /// Splits the highlighter's `<pre class="code"><code>…</code></pre>` output back into per-line
+    /// HTML. Token spans never cross line breaks, so splitting on newlines is safe.
+    public static func highlightedLines(fromCodeHTML html: String) -> [String]? {
+        let prefix = "<pre class=\"code\"><code>"
+        let suffix = "</code></pre>"
+        let html = html.hasSuffix(HighlightBudget.degradedMarker) ? String(html.dropLast(HighlightBudget.degradedMarker.count)) : html
+        guard html.hasPrefix(prefix), html.hasSuffix(suffix) else { return nil }
+        let inner = html.dropFirst(prefix.count).dropLast(suffix.count)
+        return inner.split(separator: "\n", omittingEmptySubsequences: false).map(String.init)
+    }
+
+    /// `highlightedPayload` is per-line HTML for `payloadText(in:)`; when it is nil or misaligned the
+    /// payload is rendered escaped without token colors.
+    public static func renderHTML(text: String, highlightedPayload: [String]?, showLineNumbers: Bool) -> String {
+        let lines = TextLineUtilities.displayLines(in: text)
+        let kinds = kinds(of: lines)
+        let payloadLines = highlightedPayload?.count == lines.count ? highlightedPayload : nil
+
+        return lines.enumerated().map { index, line in
+            let kind = kinds[index]
+            let content: String
+            switch kind {
+            case .fileHeader, .hunkHeader:
+                content = PlainTextRenderer.escapeHTML(line)
+            case .addition, .removal, .context:
+                let marker = line.first.map(String.init) ?? ""
+                let payload = payloadLines?[index] ?? PlainTextRenderer.escapeHTML(String(line.dropFirst()))
+                content = "<span class=\"diff-marker\">\(PlainTextRenderer.escapeHTML(marker))</span>\(payload)"
+            }
+            let gutter = showLineNumbers ? "<span class=\"ln\">\(index + 1)</span>" : ""
+            return "<div class=\"line diff-line \(kind.cssClass)\">\(gutter)<span class=\"code-line\">\(content)</span></div>"
+        }.joined()
+    }
+}
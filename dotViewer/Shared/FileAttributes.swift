@@ -138,6 +138,29 @@ public struct FileAttributes {
         return ratio < 0.05
     }
 
+    /// Text-level counterpart to the byte sniffing above, for content that was already decoded
+    /// (possibly lossily): NULs, or more than 5% control or U+FFFD replacement characters in the
+    /// first `sampleLength` scalars, mean the text is binary garbage not worth highlighting.
+    public static func isProbablyBinary(text: String, sampleLength: Int = 8 * 1024) -> Bool {
+        var total = 0
+        var suspicious = 0
+        for scalar in text.unicodeScalars.prefix(sampleLength) {
+            total += 1
+            switch scalar.value {
+            case 0:
+                return true
+            case 0x09, 0x0A, 0x0D, 0x0C:
+                continue
+            case 0x01..<0x20, 0x7F, 0xFFFD:
+                suspicious += 1
+            default:
+                continue
+            }
+        }
+        guard total > 0 else { return false }
+        return Double(suspicious) / Double(total) >= 0.05
+    }
+
     private static func encodingFromBOM(sample: Data) -> String.Encoding? {
         if sample.count >= 3,
            sample[0] == 0xEF,
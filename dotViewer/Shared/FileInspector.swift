@@ -3,8 +3,13 @@ import Foundation
 public struct FileInfo {
     public let text: String
     public let lineCount: Int
+    /// UTF-16 code units, matching `NSString.length` on the host side.
+    public let characterCount: Int
     public let fileSizeBytes: Int
     public let isTruncated: Bool
+    /// UTF-8 bytes of a leading byte-order mark removed from `text` (3, or 0 when there was none).
+    /// Add it to offsets into `text` to get offsets into the file.
+    public let byteOrderMarkLength: Int
 }
 
 public enum FileInspector {
@@ -29,9 +34,19 @@ public enum FileInspector {
         return fileInfo(from: text, fileSizeBytes: fileSizeBytes, isTruncated: isTruncated)
     }
 
+    /// Strips a leading BOM so it never reaches the highlighter as a stray first character.
     public static func fileInfo(from text: String, fileSizeBytes: Int, isTruncated: Bool) -> FileInfo {
+        let hasBOM = text.unicodeScalars.first == "\u{FEFF}"
+        let text = hasBOM ? String(text.unicodeScalars.dropFirst()) : text
         let lineCount = TextLineUtilities.visualLineCount(in: text)
-        return FileInfo(text: text, lineCount: lineCount, fileSizeBytes: fileSizeBytes, isTruncated: isTruncated)
+        return FileInfo(
+            text: text,
+            lineCount: lineCount,
+            characterCount: text.utf16.count,
+            fileSizeBytes: fileSizeBytes,
+            isTruncated: isTruncated,
+            byteOrderMarkLength: hasBOM ? 3 : 0
+        )
     }
 
     private static func decodeString(data: Data, encoding: String.Encoding) -> String {
@@ -110,6 +110,16 @@ public struct CustomExtension: Identifiable, Codable, Hashable {
     }
 }
 
+public struct LanguageMatch: Equatable, Sendable {
+    public let language: HighlightLanguage
+    public let isFallback: Bool
+
+    public init(language: HighlightLanguage, isFallback: Bool) {
+        self.language = language
+        self.isFallback = isFallback
+    }
+}
+
 /// Available highlight language identifiers for custom extensions.
 public struct HighlightLanguage: Identifiable, Hashable, Sendable {
     public let id: String
@@ -127,6 +137,60 @@ public struct HighlightLanguage: Identifiable, Hashable, Sendable {
         hasTreeSitterGrammar ? displayName : "\(displayName) (basic)"
     }
 
+    /// Lowercase, hyphenated form of the display name (`c-plus-plus`, `objective-c`), safe to use
+    /// as a cache or mapping key.
+    public var slug: String {
+        var result = ""
+        for character in displayName.lowercased() {
+            switch character {
+            case "+":
+                result += "-plus-"
+            case "#":
+                result += "-sharp-"
+            case _ where character.isLetter || character.isNumber:
+                result.append(character)
+            default:
+                result += "-"
+            }
+        }
+        return result.split(separator: "-").joined(separator: "-")
+    }
+
+    /// Looks a language up by id or display name, tolerating case and surrounding whitespace
+    /// (`"RUST"`, `" Rust "`) and aliases such as `js`. An exact id match wins.
+    public static func named(_ name: String) -> HighlightLanguage? {
+        if let exact = all.first(where: { $0.id == name }) {
+            return exact
+        }
+        let normalized = name.trimmingCharacters(in: .whitespacesAndNewlines).lowercased()
+        guard !normalized.isEmpty else { return nil }
+        if let direct = all.first(where: { $0.id.lowercased() == normalized || $0.displayName.lowercased() == normalized }) {
+            return direct
+        }
+        // Alias targets are grammar keys (`cs` → `c_sharp`), which may differ from the id.
+        guard let target = FileTypeRegistry.resolveLanguageAlias(normalized) else { return nil }
+        return all.first(where: { $0.id == target || FileTypeRegistry.resolveLanguageAlias($0.id) == target })
+    }
+
+    /// Names that explicitly ask for no highlighting, compared after trimming and lowercasing.
+    public static let plainTextSentinels: Set<String> = ["plaintext", "text", ""]
+
+    public static let plainText = all.first(where: { $0.id == "plaintext" })
+        ?? HighlightLanguage(id: "plaintext", displayName: "Plain Text")
+
+    /// The language a name resolves to, or Plain Text with `isFallback` set when nothing matched,
+    /// so the UI can label the preview "(plain text)". A plain-text sentinel (`plaintext`, `text`
+    /// or an empty name) is an intentional choice and never reported as a fallback.
+    public static func match(for name: String) -> LanguageMatch {
+        if plainTextSentinels.contains(name.trimmingCharacters(in: .whitespacesAndNewlines).lowercased()) {
+            return LanguageMatch(language: plainText, isFallback: false)
+        }
+        if let language = named(name), language.id != "plaintext" {
+            return LanguageMatch(language: language, isFallback: false)
+        }
+        return LanguageMatch(language: plainText, isFallback: true)
+    }
+
     public static let all: [HighlightLanguage] = [
         // Plain text (no highlighting)
         HighlightLanguage(id: "plaintext", displayName: "Plain Text"),
@@ -189,6 +253,12 @@ public struct HighlightLanguage: Identifiable, Hashable, Sendable {
         HighlightLanguage(id: "diff", displayName: "Diff"),
         HighlightLanguage(id: "nginx", displayName: "Nginx"),
     ]
+
+    /// `all` ordered by display name, case-insensitively and independent of locale, with ids
+    /// breaking ties, so lists built from it never reorder between runs or machines.
+    public static let alphabetical: [HighlightLanguage] = all.sorted {
+        ($0.displayName.lowercased(), $0.id) < ($1.displayName.lowercased(), $1.id)
+    }
 }
 
 /// Shared validation for custom extension/filename entries.
@@ -1,5 +1,9 @@
 import Foundation
 
+public enum FileTypeRegistryError: Error, Equatable {
+    case unknownLanguage(String)
+}
+
 /// Central registry of all supported file types with O(1) lookup.
 public final class FileTypeRegistry: @unchecked Sendable {
     public static let shared = FileTypeRegistry()
@@ -9,6 +13,9 @@ public final class FileTypeRegistry: @unchecked Sendable {
     private let extensionToType: [String: SupportedFileType]
     private let idToType: [String: SupportedFileType]
 
+    private let overrideLock = NSLock()
+    private var extensionOverrides: [String: String] = [:]
+
     private init() {
         let types = Self.loadDefaultFileTypes() ?? Self.createLegacyBuiltInTypes()
         builtInTypes = types
@@ -176,64 +183,84 @@ public final class FileTypeRegistry: @unchecked Sendable {
         return .systemsLanguages
     }
 
+    /// Short names, editor/fence aliases, and JSON `highlightLanguage` values mapped to the
+    /// tree-sitter grammar keys used by HighlightXPC.
+    private static let languageAliases: [String: String] = [
+        // Shell variants → bash grammar
+        "sh": "bash",
+        "shell": "bash",
+        "shellscript": "bash",
+        "zsh": "bash",
+        "ksh": "bash",
+        "csh": "bash",
+        "tcsh": "bash",
+        // Common short names
+        "py": "python",
+        "py3": "python",
+        "js": "javascript",
+        "jsx": "javascript",
+        "ts": "typescript",
+        "md": "markdown",
+        "yml": "yaml",
+        "rs": "rust",
+        "rb": "ruby",
+        "kt": "kotlin",
+        "golang": "go",
+        "c++": "cpp",
+        "cxx": "cpp",
+        "hpp": "cpp",
+        "cs": "c_sharp",
+        "c#": "c_sharp",
+        "objective-c": "objc",
+        "objectivec": "objc",
+        // JSON highlightLanguage → tree-sitter grammar key
+        "csharp": "c_sharp",
+        "vimscript": "vim",
+        "docker": "dockerfile",
+        "fortran77": "fortran",
+        "fortran90": "fortran",
+        "makefile": "make",
+        "pas": "pascal",
+        "conf": "ini",
+        // Infrastructure / config
+        "terraform": "hcl",
+        "svg": "xml",
+        "bat": "bash",
+        "delphi": "pascal",
+        "lhs": "haskell",
+        "vue": "html",
+        "haml": "html",
+        "erb": "html",
+        // Cross-language aliases (similar-enough syntax)
+        "less": "css",
+        "sass": "scss",
+        "styl": "css",
+        "mssql": "sql",
+        "tsql": "sql",
+        "plperl": "perl",
+        "plpython": "python",
+        "pltcl": "ini",
+        "mxml": "xml",
+        "crystal": "ruby",
+        "gdscript": "python",
+        "lisp": "clojure",
+        "coffeescript": "javascript",
+        "solidity": "javascript",
+    ]
+
+    /// Resolves a short or alternate language name (e.g. `js`, `yml`, `c++`) to its canonical
+    /// highlight language. Returns nil when `name` is not a known alias.
+    public static func resolveLanguageAlias(_ name: String) -> String? {
+        languageAliases[name.trimmingCharacters(in: .whitespaces).lowercased()]
+    }
+
     private static func resolveHighlightLanguage(
         for item: DefaultFileType,
         extensions: [String]
     ) -> String {
         let lower = item.highlightLanguage?.lowercased() ?? item.id.lowercased()
 
-        let aliases: [String: String] = [
-            // Shell variants → bash grammar
-            "sh": "bash",
-            "shell": "bash",
-            "shellscript": "bash",
-            "zsh": "bash",
-            "ksh": "bash",
-            "csh": "bash",
-            "tcsh": "bash",
-            // Common short names
-            "py": "python",
-            "js": "javascript",
-            "jsx": "javascript",
-            "ts": "typescript",
-            "md": "markdown",
-            "yml": "yaml",
-            // JSON highlightLanguage → tree-sitter grammar key
-            "csharp": "c_sharp",
-            "vimscript": "vim",
-            "docker": "dockerfile",
-            "fortran77": "fortran",
-            "fortran90": "fortran",
-            "makefile": "make",
-            "pas": "pascal",
-            "conf": "ini",
-            // Infrastructure / config
-            "terraform": "hcl",
-            "svg": "xml",
-            "bat": "bash",
-            "delphi": "pascal",
-            "lhs": "haskell",
-            "vue": "html",
-            "haml": "html",
-            "erb": "html",
-            // Cross-language aliases (similar-enough syntax)
-            "less": "css",
-            "sass": "scss",
-            "styl": "css",
-            "mssql": "sql",
-            "tsql": "sql",
-            "plperl": "perl",
-            "plpython": "python",
-            "pltcl": "ini",
-            "mxml": "xml",
-            "crystal": "ruby",
-            "gdscript": "python",
-            "lisp": "clojure",
-            "coffeescript": "javascript",
-            "solidity": "javascript",
-        ]
-
-        if let alias = aliases[lower] {
+        if let alias = languageAliases[lower] {
             return alias
         }
 
@@ -675,9 +702,45 @@ public final class FileTypeRegistry: @unchecked Sendable {
         return true
     }
 
+    /// Maps `ext` to `language` for this process, ahead of custom and built-in extensions. Unlike
+    /// `SharedSettings.customExtensions` nothing is persisted. Throws when `language` is not a known
+    /// highlight language.
+    public func registerExtensionOverride(_ ext: String, language: String) throws {
+        var key = ext.trimmingCharacters(in: .whitespaces).lowercased()
+        if key.hasPrefix(".") {
+            key.removeFirst()
+        }
+        guard !key.isEmpty, let resolved = HighlightLanguage.named(language) else {
+            throw FileTypeRegistryError.unknownLanguage(language)
+        }
+        overrideLock.lock()
+        defer { overrideLock.unlock() }
+        extensionOverrides[key] = resolved.id
+    }
+
+    public func removeExtensionOverride(_ ext: String) {
+        var key = ext.trimmingCharacters(in: .whitespaces).lowercased()
+        if key.hasPrefix(".") {
+            key.removeFirst()
+        }
+        overrideLock.lock()
+        defer { overrideLock.unlock() }
+        extensionOverrides.removeValue(forKey: key)
+    }
+
+    private func extensionOverride(for ext: String) -> String? {
+        overrideLock.lock()
+        defer { overrideLock.unlock() }
+        return extensionOverrides[ext]
+    }
+
     public func highlightLanguage(for ext: String) -> String? {
         let lowered = ext.lowercased()
 
+        if let override = extensionOverride(for: lowered) {
+            return override
+        }
+
         // Check custom extensions first — enables overrides and filename mappings
         if let custom = SharedSettings.shared.customExtensions.first(where: { $0.extensionName == lowered }) {
             return custom.highlightLanguage
@@ -704,12 +767,73 @@ public final class FileTypeRegistry: @unchecked Sendable {
         return nil
     }
 
+    /// Whether a language name or extension (with or without a leading dot) resolves to something
+    /// HighlightXPC will actually color, rather than falling back to plain text.
+    public func isHighlightSupported(_ nameOrExtension: String) -> Bool {
+        var key = nameOrExtension.trimmingCharacters(in: .whitespaces).lowercased()
+        if key.hasPrefix(".") {
+            key.removeFirst()
+        }
+        guard !key.isEmpty else { return false }
+
+        let language = highlightLanguage(for: key)
+            ?? Self.resolveLanguageAlias(key)
+            ?? HighlightLanguage.all.first(where: { $0.id == key })?.id
+        guard let language else { return false }
+        return language != "plaintext"
+    }
+
+    /// The language a name or extension (with or without a leading dot) resolves to. Nil when
+    /// nothing matches.
+    public func language(forNameOrExtension nameOrExtension: String) -> HighlightLanguage? {
+        var key = nameOrExtension.trimmingCharacters(in: .whitespaces)
+        if key.hasPrefix(".") {
+            key.removeFirst()
+        }
+        guard !key.isEmpty else { return nil }
+
+        if let language = HighlightLanguage.named(key) {
+            return language
+        }
+        // Extensions map to grammar keys (`m` → `objc`), which can differ from the language id.
+        guard let grammar = highlightLanguage(for: key.lowercased()).map(Self.canonicalLanguage) else { return nil }
+        return HighlightLanguage.all.first(where: { Self.canonicalLanguage($0.id) == grammar })
+    }
+
+    /// Stable slug of the language a name or extension resolves to, e.g. `c-plus-plus` for `C++`
+    /// or `cc`. Nil when nothing matches.
+    public func languageSlug(for nameOrExtension: String) -> String? {
+        language(forNameOrExtension: nameOrExtension)?.slug
+    }
+
+    /// Built-in extensions already highlighted as `language`, sorted. Empty for unknown languages.
+    public func extensions(forLanguage language: String) -> [String] {
+        let target = Self.canonicalLanguage(language)
+        guard !target.isEmpty else { return [] }
+
+        var result = Set<String>()
+        for type in builtInTypes where Self.canonicalLanguage(type.highlightLanguage) == target {
+            result.formUnion(type.extensions)
+        }
+        return result.sorted()
+    }
+
+    private static func canonicalLanguage(_ language: String) -> String {
+        let lowered = language.trimmingCharacters(in: .whitespaces).lowercased()
+        return resolveLanguageAlias(lowered) ?? lowered
+    }
+
     public func fileType(for ext: String) -> SupportedFileType? {
         extensionToType[ext.lowercased()]
     }
 
+    /// Human-readable language name for an extension, with or without a leading dot
+    /// (`.rs` and `rs` both give "Rust"). Nil for unknown extensions.
     public func displayName(for ext: String) -> String? {
-        let lowered = ext.lowercased()
+        var lowered = ext.trimmingCharacters(in: .whitespaces).lowercased()
+        if lowered.hasPrefix(".") {
+            lowered.removeFirst()
+        }
         if let custom = SharedSettings.shared.customExtensions.first(where: { $0.extensionName == lowered }) {
             return custom.displayName
         }
@@ -70,4 +70,19 @@ public enum FileTypeResolution {
         // No match — return extension if available, otherwise full name
         return !pathExt.isEmpty ? pathExt : fileNameNoLeadingDot
     }
+
+    /// Highlight language for a bare filename such as `Dockerfile`, `Makefile`, `CMakeLists.txt`
+    /// or `.gitignore`, using the same resolution as previews. Nil when nothing matches.
+    public static func highlightLanguage(forFilename filename: String, registry: FileTypeRegistry = .shared) -> String? {
+        let url = URL(fileURLWithPath: filename)
+        return registry.highlightLanguageByFilename(url.lastPathComponent)
+            ?? registry.highlightLanguage(for: bestKey(for: url, registry: registry))
+    }
+
+    /// Highlight language for a file on disk: its name and extension first, then the shebang in
+    /// the first line of `text`. Nil when neither identifies a language.
+    public static func highlightLanguage(for url: URL, text: String, registry: FileTypeRegistry = .shared) -> String? {
+        highlightLanguage(forFilename: url.lastPathComponent, registry: registry)
+            ?? ShebangLanguageDetector.detect(in: text)?.languageId
+    }
 }
@@ -0,0 +1,94 @@
+import Foundation
+
+public struct FoldNode: Equatable, Sendable {
+    /// 1-based display lines covered, header line first.
+    public let lines: ClosedRange<Int>
+    /// The header line's highlighted runs, with surrounding whitespace trimmed, shown when the
+    /// region is collapsed (e.g. a function signature).
+    public let summary: [HighlightRun]
+    public let children: [FoldNode]
+
+    public init(lines: ClosedRange<Int>, summary: [HighlightRun], children: [FoldNode]) {
+        self.lines = lines
+        self.summary = summary
+        self.children = children
+    }
+
+    public var headerLine: Int { lines.lowerBound }
+
+    /// `summary` as plain text.
+    public var summaryText: String { HighlightRuns.plainText(of: summary) }
+}
+
+/// Builds nested fold regions from indentation: a line followed by deeper-indented lines opens a
+/// region that runs until indentation returns to the header's level. A closing line at that level
+/// (`}`, `]`, `)`, `end`) belongs to the region, so brace and indentation languages both work.
+public enum FoldRegionTree {
+    private static let closingPrefixes = ["}", "]", ")", "end"]
+
+    /// `tokens` come from a whole-document parse and color each region's summary.
+    public static func tree(in text: String, tokens: [HighlightToken], tabWidth: Int = 4) -> [FoldNode] {
+        let lines = TextLineUtilities.displayLines(in: text)
+        var highlighted: [Int: [HighlightRun]] = [:]
+        for line in HighlightRuns.lines(in: text, tokens: tokens, range: 1...Int.max) {
+            highlighted[line.number] = line.runs
+        }
+        let widths: [Int?] = lines.map {
+            TextIndentation.isBlank($0) ? nil : TextIndentation.visualIndentWidth(of: $0, tabWidth: tabWidth)
+        }
+
+        func nextContent(after index: Int, before end: Int) -> Int? {
+            var cursor = index + 1
+            while cursor < end {
+                if widths[cursor] != nil { return cursor }
+                cursor += 1
+            }
+            return nil
+        }
+
+        func nodes(from start: Int, to end: Int) -> [FoldNode] {
+            var result: [FoldNode] = []
+            var index = start
+            while index < end {
+                guard let width = widths[index],
+                      let next = nextContent(after: index, before: end),
+                      let nextWidth = widths[next], nextWidth > width
+                else {
+                    index += 1
+                    continue
+                }
+
+                var stop = next
+                while stop < end, widths[stop].map({ $0 > width }) ?? true {
+                    stop += 1
+                }
+                var last = stop - 1
+                while last > index, widths[last] == nil {
+                    last -= 1
+                }
+                var resume = stop
+                if stop < end, isClosingLine(lines[stop]) {
+                    last = stop
+                    resume = stop + 1
+                }
+
+                result.append(FoldNode(
+                    lines: (index + 1)...(last + 1),
+                    summary: HighlightRuns.trimmingWhitespace(highlighted[index + 1] ?? []),
+                    children: nodes(from: index + 1, to: stop)
+                ))
+                index = resume
+            }
+            return result
+        }
+
+        return nodes(from: 0, to: lines.count)
+    }
+
+    private static func isClosingLine(_ line: String) -> Bool {
+        let trimmed = line.trimmingCharacters(in: .whitespaces)
+        return closingPrefixes.contains { prefix in
+            trimmed.hasPrefix(prefix) && (prefix != "end" || trimmed.dropFirst(3).first?.isLetter != true)
+        }
+    }
+}
@@ -0,0 +1,44 @@
+import Foundation
+
+/// Keeps byte-offset spans from ending inside a grapheme cluster (a ZWJ emoji sequence, a flag,
+/// a base letter plus combining accent), where a split would render as broken glyphs.
+public enum GraphemeBoundaries {
+    /// UTF-8 offsets where clusters of `text` start, followed by the end offset. `\r\n` is one
+    /// cluster to Swift but stays two here, since line handling splits between the two bytes.
+    public static func offsets(in text: String) -> [Int] {
+        var offsets = [0]
+        var offset = 0
+        for character in text {
+            if character == "\r\n" {
+                offsets.append(offset + 1)
+            }
+            offset += character.utf8.count
+            offsets.append(offset)
+        }
+        return offsets
+    }
+
+    /// Moves both edges of every span forward to the next cluster boundary, so a partial cluster
+    /// joins the span its first byte belongs to. Order is preserved; spans that collapse are dropped.
+    public static func snap(_ spans: [HighlightToken], to boundaries: [Int]) -> [HighlightToken] {
+        spans.compactMap { span in
+            let start = ceiling(span.s, in: boundaries)
+            let end = ceiling(span.e, in: boundaries)
+            return start < end ? HighlightToken(s: start, e: end, c: span.c) : nil
+        }
+    }
+
+    static func ceiling(_ offset: Int, in boundaries: [Int]) -> Int {
+        var low = 0
+        var high = boundaries.count
+        while low < high {
+            let middle = (low + high) / 2
+            if boundaries[middle] < offset {
+                low = middle + 1
+            } else {
+                high = middle
+            }
+        }
+        return low < boundaries.count ? boundaries[low] : boundaries.last ?? offset
+    }
+}
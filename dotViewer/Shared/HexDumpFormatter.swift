@@ -0,0 +1,37 @@
+import Foundation
+
+/// Classic `offset  hex bytes  |ascii|` rows for binary content, as styled lines so any renderer
+/// can color them with the active palette: offsets as numbers, printable ASCII as strings and
+/// non-printable bytes (shown as `.`) as comments.
+public enum HexDumpFormatter {
+    public static func lines(for data: Data, bytesPerRow: Int = 16) -> [HighlightedLine] {
+        let width = max(bytesPerRow, 1)
+        let bytes = [UInt8](data)
+        var lines: [HighlightedLine] = []
+
+        for rowStart in stride(from: 0, to: bytes.count, by: width) {
+            let row = bytes[rowStart..<min(rowStart + width, bytes.count)]
+            var hex = row.map { String(format: "%02x", $0) }.joined(separator: " ")
+            // Pad short final rows so the ASCII column stays aligned.
+            hex += String(repeating: " ", count: (width - row.count) * 3)
+
+            var runs = [
+                HighlightRun(tokenType: .number, text: String(format: "%08x", rowStart)),
+                HighlightRun(tokenType: nil, text: "  " + hex + "  |"),
+            ]
+            for byte in row {
+                let isPrintable = (0x20...0x7E).contains(byte)
+                let type: TokenType = isPrintable ? .string : .comment
+                let text = isPrintable ? String(UnicodeScalar(byte)) : "."
+                if let last = runs.last, last.tokenType == type {
+                    runs[runs.count - 1] = HighlightRun(tokenType: type, text: last.text + text)
+                } else {
+                    runs.append(HighlightRun(tokenType: type, text: text))
+                }
+            }
+            runs.append(HighlightRun(tokenType: nil, text: "|"))
+            lines.append(HighlightedLine(number: lines.count + 1, runs: runs))
+        }
+        return lines
+    }
+}
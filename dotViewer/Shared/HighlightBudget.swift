@@ -0,0 +1,26 @@
+import Foundation
+
+/// Soft time limit for one highlight pass. Once it runs out the highlighter stops collecting
+/// captures, so the rest of the file renders as plain text instead of the preview appearing
+/// frozen on adversarial input, and `isDegraded` records that it happened.
+public final class HighlightBudget {
+    public static let defaultLimit: Duration = .seconds(2)
+    /// Appended to highlight HTML that was cut short by its budget.
+    public static let degradedMarker = "<!-- dotviewer:highlight-degraded -->"
+
+    private let clock = ContinuousClock()
+    private let deadline: ContinuousClock.Instant
+    public private(set) var isDegraded = false
+
+    public init(limit: Duration = defaultLimit) {
+        deadline = clock.now.advanced(by: limit)
+    }
+
+    /// True once the limit has passed. Latches `isDegraded`.
+    public func isExhausted() -> Bool {
+        if !isDegraded, clock.now >= deadline {
+            isDegraded = true
+        }
+        return isDegraded
+    }
+}
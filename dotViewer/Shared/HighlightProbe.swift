@@ -0,0 +1,36 @@
+import Foundation
+
+/// Metadata for a sample without highlighting it, so settings UI can label a preview and paint
+/// its background before committing to a full highlight.
+public struct HighlightProbe: Equatable, Sendable {
+    /// Display name of the resolved language, e.g. `Rust`.
+    public let detectedLanguage: String
+    /// True when nothing matched and the sample would render as plain text.
+    public let isFallback: Bool
+    public let background: String
+
+    public init(detectedLanguage: String, isFallback: Bool, background: String) {
+        self.detectedLanguage = detectedLanguage
+        self.isFallback = isFallback
+        self.background = background
+    }
+
+    /// Resolves `nameOrExtension`, then the sample's shebang, and the theme's background for
+    /// `systemIsDark`. No tokens are produced.
+    public static func probe(
+        sample: String,
+        nameOrExtension: String,
+        theme: String,
+        systemIsDark: Bool = false,
+        registry: FileTypeRegistry = .shared
+    ) -> HighlightProbe {
+        let resolved = registry.language(forNameOrExtension: nameOrExtension)
+            ?? ShebangLanguageDetector.detect(in: sample).flatMap { HighlightLanguage.named($0.languageId) }
+        let match = resolved.map { HighlightLanguage.match(for: $0.id) }
+        return HighlightProbe(
+            detectedLanguage: match?.language.displayName ?? HighlightLanguage.plainText.displayName,
+            isFallback: match?.isFallback ?? true,
+            background: ThemePalette.palette(for: theme, systemIsDark: systemIsDark).background
+        )
+    }
+}
@@ -37,4 +37,6 @@ public enum HighlightFallbackReason: String, Error {
     case timeout
     case highlightingFailed
     case cancelled
+    case binaryContent
+    case unreadableFile
 }
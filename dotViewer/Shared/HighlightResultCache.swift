@@ -0,0 +1,155 @@
+import CryptoKit
+import Foundation
+
+/// LRU of highlight HTML keyed by a hash of the content and highlight options, so Quick Look
+/// re-requesting identical content skips the XPC round trip. It is bounded both by entry count
+/// and by the total UTF-8 size of the stored HTML. Unlike `PreviewCache` it is in-memory only and
+/// independent of the file's path or modification date.
+public final class HighlightResultCache: @unchecked Sendable {
+    public static let shared = HighlightResultCache()
+
+    /// Recency is a doubly linked list threaded through the entries by key, so hits and
+    /// evictions don't scan.
+    private struct Entry {
+        var html: String
+        var cost: Int
+        var older: String?
+        var newer: String?
+    }
+
+    private let lock = NSLock()
+    private var capacity: Int
+    private var maxBytes: Int
+    private var entries: [String: Entry] = [:]
+    private var oldest: String?
+    private var newest: String?
+    private var bytes = 0
+    private var hits = 0
+
+    public init(capacity: Int = 32, maxBytes: Int = 8 * 1024 * 1024) {
+        self.capacity = max(capacity, 0)
+        self.maxBytes = max(maxBytes, 0)
+    }
+
+    public static func key(code: String, language: String, theme: String, showLineNumbers: Bool) -> String {
+        var hasher = SHA256()
+        for part in [language, theme, showLineNumbers ? "ln1" : "ln0", code] {
+            hasher.update(data: Data(part.utf8))
+            hasher.update(data: Data([0]))
+        }
+        return hasher.finalize().map { String(format: "%02x", $0) }.joined()
+    }
+
+    public func html(forKey key: String) -> String? {
+        lock.lock()
+        defer { lock.unlock() }
+        guard let html = entries[key]?.html else { return nil }
+        hits += 1
+        unlink(key)
+        linkNewest(key)
+        return html
+    }
+
+    /// Output the service cut short when its time budget ran out is not stored, so one slow run
+    /// doesn't pin the degraded preview for that content. HTML larger than the byte limit on its
+    /// own is not stored either.
+    public func store(_ html: String, forKey key: String) {
+        guard !html.hasSuffix(HighlightBudget.degradedMarker) else { return }
+        let cost = html.utf8.count
+        lock.lock()
+        defer { lock.unlock() }
+        if entries[key] != nil {
+            remove(key)
+        }
+        guard capacity > 0, cost <= maxBytes else { return }
+        entries[key] = Entry(html: html, cost: cost)
+        bytes += cost
+        linkNewest(key)
+        evictOverflow()
+    }
+
+    /// Number of lookups served from the cache since creation or the last `clear()`.
+    public var hitCount: Int {
+        lock.lock()
+        defer { lock.unlock() }
+        return hits
+    }
+
+    public var count: Int {
+        lock.lock()
+        defer { lock.unlock() }
+        return entries.count
+    }
+
+    /// UTF-8 size of all stored HTML.
+    public var totalBytes: Int {
+        lock.lock()
+        defer { lock.unlock() }
+        return bytes
+    }
+
+    public func clear() {
+        lock.lock()
+        defer { lock.unlock() }
+        entries.removeAll()
+        oldest = nil
+        newest = nil
+        bytes = 0
+        hits = 0
+    }
+
+    /// Changes the maximum number of entries, evicting the least recently used ones if needed.
+    /// A capacity of zero disables caching.
+    public func setCapacity(_ newCapacity: Int) {
+        lock.lock()
+        defer { lock.unlock() }
+        capacity = max(newCapacity, 0)
+        evictOverflow()
+    }
+
+    /// Changes the total size limit, evicting the least recently used entries if needed.
+    public func setMaxBytes(_ newMaxBytes: Int) {
+        lock.lock()
+        defer { lock.unlock() }
+        maxBytes = max(newMaxBytes, 0)
+        evictOverflow()
+    }
+
+    private func linkNewest(_ key: String) {
+        entries[key]?.older = newest
+        entries[key]?.newer = nil
+        if let newest {
+            entries[newest]?.newer = key
+        } else {
+            oldest = key
+        }
+        newest = key
+    }
+
+    private func unlink(_ key: String) {
+        guard let entry = entries[key] else { return }
+        if let older = entry.older {
+            entries[older]?.newer = entry.newer
+        } else {
+            oldest = entry.newer
+        }
+        if let newer = entry.newer {
+            entries[newer]?.older = entry.older
+        } else {
+            newest = entry.older
+        }
+    }
+
+    private func remove(_ key: String) {
+        unlink(key)
+        if let entry = entries.removeValue(forKey: key) {
+            bytes -= entry.cost
+        }
+    }
+
+    private func evictOverflow() {
+        while entries.count > capacity || bytes > maxBytes, let oldest {
+            remove(oldest)
+        }
+    }
+}
@@ -0,0 +1,463 @@
+import Foundation
+
+public struct HighlightRun: Codable, Equatable, Sendable {
+    /// nil for text outside any recognized token.
+    public let tokenType: TokenType?
+    public let text: String
+    /// Tint behind just this run, such as marked trailing whitespace.
+    public let background: String?
+
+    public init(tokenType: TokenType?, text: String, background: String? = nil) {
+        self.tokenType = tokenType
+        self.text = text
+        self.background = background
+    }
+}
+
+/// What line-structured output does with spaces and tabs at the end of each line.
+public enum TrailingWhitespace: Sendable {
+    case keep
+    /// Split into their own runs with `HighlightRuns.trailingWhitespaceBackground`.
+    case mark
+    case strip
+}
+
+public struct HighlightedLine: Codable, Equatable, Sendable {
+    /// 1-based line number.
+    public let number: Int
+    public let runs: [HighlightRun]
+    /// Tint behind the whole line (e.g. a lint overlay); token foregrounds are unchanged.
+    public let background: String?
+    /// True for the second and later visual rows of a soft-wrapped source line.
+    public let isContinuation: Bool
+
+    public init(number: Int, runs: [HighlightRun], background: String? = nil, isContinuation: Bool = false) {
+        self.number = number
+        self.runs = runs
+        self.background = background
+        self.isContinuation = isContinuation
+    }
+
+    /// True when any run holds right-to-left letters (e.g. a Hebrew comment), so the host can set
+    /// this line's paragraph direction.
+    public var containsRightToLeft: Bool {
+        runs.contains { BidiControlDetector.containsRightToLeftText($0.text) }
+    }
+}
+
+/// A background tint for one 1-based line, supplied by tools such as linters.
+public struct LineAnnotation: Codable, Equatable, Sendable {
+    public let line: Int
+    public let background: String
+
+    public init(line: Int, background: String) {
+        self.line = line
+        self.background = background
+    }
+}
+
+/// One run of a `PalettedRuns` result; `colorIndex` points into its `colors`.
+public struct PalettedRun: Codable, Equatable, Sendable {
+    public let colorIndex: Int
+    public let text: String
+
+    public init(colorIndex: Int, text: String) {
+        self.colorIndex = colorIndex
+        self.text = text
+    }
+}
+
+/// Highlight output with each distinct color stored once, for exporters and compact caching.
+public struct PalettedRuns: Codable, Equatable, Sendable {
+    public let background: String
+    /// Distinct `#RRGGBB` foregrounds; index 0 is the theme's text color.
+    public let colors: [String]
+    public let runs: [PalettedRun]
+
+    public init(background: String, colors: [String], runs: [PalettedRun]) {
+        self.background = background
+        self.colors = colors
+        self.runs = runs
+    }
+
+    public func color(of run: PalettedRun) -> String {
+        colors.indices.contains(run.colorIndex) ? colors[run.colorIndex] : colors.first ?? ""
+    }
+
+    /// The source text exactly as highlighted, newlines included, for copy actions.
+    public var plainText: String {
+        runs.map(\.text).joined()
+    }
+}
+
+/// `PalettedRuns` as one concatenated string plus a flat stream of alternating color index and
+/// UTF-8 length, so very large files cross process boundaries without one string per run.
+public struct RunLengthStyles: Codable, Equatable, Sendable {
+    public let background: String
+    public let colors: [String]
+    /// `[colorIndex, byteLength, colorIndex, byteLength, …]`.
+    public let stream: [UInt32]
+    public let text: String
+
+    public init(background: String, colors: [String], stream: [UInt32], text: String) {
+        self.background = background
+        self.colors = colors
+        self.stream = stream
+        self.text = text
+    }
+
+    public init(_ paletted: PalettedRuns) {
+        var stream: [UInt32] = []
+        stream.reserveCapacity(paletted.runs.count * 2)
+        for run in paletted.runs {
+            stream.append(UInt32(run.colorIndex))
+            stream.append(UInt32(run.text.utf8.count))
+        }
+        self.init(
+            background: paletted.background,
+            colors: paletted.colors,
+            stream: stream,
+            text: paletted.runs.map(\.text).joined()
+        )
+    }
+
+    /// Rebuilds the runs by walking byte offsets into `text`. A stream that overruns `text` is
+    /// clamped rather than trapping.
+    public func decoded() -> PalettedRuns {
+        let bytes = Array(text.utf8)
+        var runs: [PalettedRun] = []
+        var offset = 0
+        for pair in stride(from: 0, to: stream.count - 1, by: 2) {
+            let end = min(offset + Int(stream[pair + 1]), bytes.count)
+            runs.append(PalettedRun(colorIndex: Int(stream[pair]), text: String(decoding: bytes[offset..<end], as: UTF8.self)))
+            offset = end
+        }
+        return PalettedRuns(background: background, colors: colors, runs: runs)
+    }
+}
+
+/// Both appearances of one highlight, for switching instantly when the system appearance changes.
+public struct DualPalettedRuns: Codable, Equatable, Sendable {
+    public let light: PalettedRuns
+    public let dark: PalettedRuns
+
+    public init(light: PalettedRuns, dark: PalettedRuns) {
+        self.light = light
+        self.dark = dark
+    }
+}
+
+/// Flattens byte-offset `HighlightToken`s into consecutive text runs for non-HTML renderers.
+public enum HighlightRuns {
+    /// Maximal runs of `code` sharing one token type, covering every byte. Where tokens overlap the
+    /// later one wins; unknown token classes count as plain text. A grapheme cluster (including a
+    /// CRLF line break) always takes the type of its first byte, so no run ends mid-cluster.
+    public static func runs(in code: String, tokens: [HighlightToken]) -> [HighlightRun] {
+        let bytes = Array(code.utf8)
+        var classes = [TokenType?](repeating: nil, count: bytes.count)
+        for token in tokens {
+            guard let type = TokenType(rawValue: token.c) else { continue }
+            let start = max(token.s, 0)
+            let end = min(token.e, bytes.count)
+            guard start < end else { continue }
+            for index in start..<end {
+                classes[index] = type
+            }
+        }
+
+        for index in bytes.indices.dropLast() where bytes[index] == 0x0D && bytes[index + 1] == 0x0A {
+            classes[index + 1] = classes[index]
+        }
+        if !bytes.allSatisfy({ $0 < 0x80 }) {
+            let boundaries = GraphemeBoundaries.offsets(in: code)
+            for (start, end) in zip(boundaries, boundaries.dropFirst()) where end - start > 1 {
+                for index in (start + 1)..<end {
+                    classes[index] = classes[start]
+                }
+            }
+        }
+
+        var runs: [HighlightRun] = []
+        var runStart = 0
+        while runStart < bytes.count {
+            var runEnd = runStart + 1
+            while runEnd < bytes.count, classes[runEnd] == classes[runStart] {
+                runEnd += 1
+            }
+            runs.append(HighlightRun(
+                tokenType: classes[runStart],
+                text: String(decoding: bytes[runStart..<runEnd], as: UTF8.self)
+            ))
+            runStart = runEnd
+        }
+        return runs
+    }
+
+    /// The source text the runs were built from, byte for byte.
+    public static func plainText(of runs: [HighlightRun]) -> String {
+        runs.map(\.text).joined()
+    }
+
+    /// `runs(in:tokens:)` without line breaks when `emitNewlines` is false, for hosts that lay out
+    /// lines themselves: runs are split at each line break (`\n`, `\r\n` or `\r`) and the breaks dropped.
+    public static func runs(in code: String, tokens: [HighlightToken], emitNewlines: Bool) -> [HighlightRun] {
+        let all = runs(in: code, tokens: tokens)
+        guard !emitNewlines else { return all }
+        return all.flatMap { run in
+            run.text.split(whereSeparator: isLineBreak).map { HighlightRun(tokenType: run.tokenType, text: String($0)) }
+        }
+    }
+
+    /// `runs(in:tokens:)` with no run longer than `maxSpanCharacters` Characters, for renderers that
+    /// slow down on very long single spans such as a minified string literal. Longer runs are cut
+    /// into consecutive runs of the same type, so the text and every byte offset are unchanged.
+    /// nil leaves runs whole.
+    public static func runs(in code: String, tokens: [HighlightToken], maxSpanCharacters: Int?) -> [HighlightRun] {
+        let all = runs(in: code, tokens: tokens)
+        guard let maxSpanCharacters else { return all }
+        let limit = max(maxSpanCharacters, 1)
+        return all.flatMap { run -> [HighlightRun] in
+            guard run.text.count > limit else { return [run] }
+            var pieces: [HighlightRun] = []
+            var index = run.text.startIndex
+            while index < run.text.endIndex {
+                let end = run.text.index(index, offsetBy: limit, limitedBy: run.text.endIndex) ?? run.text.endIndex
+                pieces.append(HighlightRun(tokenType: run.tokenType, text: String(run.text[index..<end]), background: run.background))
+                index = end
+            }
+            return pieces
+        }
+    }
+
+    /// Runs for the 1-based lines in `range` only. Tokens come from a whole-document parse, so a
+    /// block comment or string opened above the range still colors the visible lines. Lines past
+    /// the end of `code` are omitted; runs never contain the newline itself.
+    public static func lines(in code: String, tokens: [HighlightToken], range: ClosedRange<Int>) -> [HighlightedLine] {
+        var result: [HighlightedLine] = []
+        var lineNumber = 1
+        var current: [HighlightRun] = []
+
+        func finishLine() {
+            if range.contains(lineNumber) {
+                result.append(HighlightedLine(number: lineNumber, runs: current))
+            }
+            current = []
+            lineNumber += 1
+        }
+
+        for run in runs(in: code, tokens: tokens) {
+            let pieces = run.text.split(omittingEmptySubsequences: false, whereSeparator: isLineBreak)
+            for (index, piece) in pieces.enumerated() {
+                if index > 0 {
+                    finishLine()
+                    if lineNumber > range.upperBound { return result }
+                }
+                if !piece.isEmpty, range.contains(lineNumber) {
+                    current.append(HighlightRun(tokenType: run.tokenType, text: String(piece)))
+                }
+            }
+        }
+        if let last = code.last, !isLineBreak(last) {
+            finishLine()
+        }
+        return result
+    }
+
+    /// Line breaks as `TextLineUtilities.displayLines` counts them. `\r\n` is a single Character,
+    /// so splitting on `"\n"` alone would leave CRLF text as one line.
+    static func isLineBreak(_ character: Character) -> Bool {
+        character == "\n" || character == "\r\n" || character == "\r"
+    }
+
+    /// Drops leading and trailing spaces and tabs, removing runs that were only whitespace.
+    static func trimmingWhitespace(_ runs: [HighlightRun]) -> [HighlightRun] {
+        let isSpace: (Character) -> Bool = { $0 == " " || $0 == "\t" }
+        var result = runs
+        while let first = result.first {
+            let text = String(first.text.drop(while: isSpace))
+            result.removeFirst()
+            if !text.isEmpty {
+                result.insert(HighlightRun(tokenType: first.tokenType, text: text, background: first.background), at: 0)
+                break
+            }
+        }
+        while let last = result.last {
+            let text = String(last.text.reversed().drop(while: isSpace).reversed())
+            result.removeLast()
+            if !text.isEmpty {
+                result.append(HighlightRun(tokenType: last.tokenType, text: text, background: last.background))
+                break
+            }
+        }
+        return result
+    }
+
+    /// Every line of `code` with `annotations` applied. Annotations for lines that don't exist are
+    /// ignored; when a line is annotated twice the last one wins.
+    public static func lines(
+        in code: String,
+        tokens: [HighlightToken],
+        annotations: [LineAnnotation]
+    ) -> [HighlightedLine] {
+        var backgrounds: [Int: String] = [:]
+        for annotation in annotations {
+            backgrounds[annotation.line] = annotation.background
+        }
+        return lines(in: code, tokens: tokens, range: 1...Int.max).map { line in
+            HighlightedLine(number: line.number, runs: line.runs, background: backgrounds[line.number])
+        }
+    }
+
+    public static let trailingWhitespaceBackground = "#FF000033"
+
+    /// Every line of `code` with trailing spaces and tabs kept, marked or stripped.
+    public static func lines(
+        in code: String,
+        tokens: [HighlightToken],
+        trailingWhitespace: TrailingWhitespace
+    ) -> [HighlightedLine] {
+        let all = lines(in: code, tokens: tokens, range: 1...Int.max)
+        guard trailingWhitespace != .keep else { return all }
+
+        return all.map { line in
+            var runs = line.runs
+            var trailing: [HighlightRun] = []
+            while let last = runs.last {
+                let kept = last.text.reversed().drop(while: { $0 == " " || $0 == "\t" }).reversed()
+                let whitespace = String(last.text.dropFirst(kept.count))
+                if !whitespace.isEmpty {
+                    trailing.insert(HighlightRun(tokenType: last.tokenType, text: whitespace, background: trailingWhitespaceBackground), at: 0)
+                }
+                runs.removeLast()
+                if !kept.isEmpty {
+                    runs.append(HighlightRun(tokenType: last.tokenType, text: String(kept), background: last.background))
+                    break
+                }
+            }
+            if trailingWhitespace == .mark {
+                runs += trailing
+            }
+            return HighlightedLine(number: line.number, runs: runs, background: line.background, isContinuation: line.isContinuation)
+        }
+    }
+
+    /// Visual rows of at most `maxColumns` columns each; a wrapped source line keeps its number and
+    /// marks the extra rows as continuations. Columns follow `TextIndentation.visualWidth`: tabs
+    /// advance to the next `tabWidth` stop and wide CJK characters and emoji take two. Splits fall
+    /// between grapheme clusters, so emoji sequences and combining marks are never broken.
+    public static func wrappedLines(
+        in code: String,
+        tokens: [HighlightToken],
+        maxColumns: Int,
+        tabWidth: Int = 4
+    ) -> [HighlightedLine] {
+        let width = max(maxColumns, 1)
+        var result: [HighlightedLine] = []
+
+        for line in lines(in: code, tokens: tokens, range: 1...Int.max) {
+            var row: [HighlightRun] = []
+            var used = 0
+            var isContinuation = false
+
+            func finishRow() {
+                result.append(HighlightedLine(number: line.number, runs: row, background: line.background, isContinuation: isContinuation))
+                row = []
+                used = 0
+                isContinuation = true
+            }
+
+            for run in line.runs {
+                var piece = ""
+                for character in run.text {
+                    var columns = TextIndentation.visualWidth(of: String(character), tabWidth: tabWidth, startColumn: used)
+                    if used > 0, used + columns > width {
+                        if !piece.isEmpty {
+                            row.append(HighlightRun(tokenType: run.tokenType, text: piece))
+                            piece = ""
+                        }
+                        finishRow()
+                        columns = TextIndentation.visualWidth(of: String(character), tabWidth: tabWidth)
+                    }
+                    piece.append(character)
+                    used += columns
+                }
+                if !piece.isEmpty {
+                    row.append(HighlightRun(tokenType: run.tokenType, text: piece))
+                }
+            }
+            finishRow()
+        }
+        return result
+    }
+
+    /// `runs(in:tokens:)` resolved against `palette`, with adjacent runs of the same color merged.
+    public static func paletted(in code: String, tokens: [HighlightToken], palette: ThemePalette) -> PalettedRuns {
+        paletted(runs(in: code, tokens: tokens), palette: palette, mergingAdjacent: true)
+    }
+
+    /// Light and dark renderings from a single pass over `tokens`. Runs are not merged, so the two
+    /// results line up run for run and only their colors differ.
+    public static func dual(in code: String, tokens: [HighlightToken], light: ThemePalette, dark: ThemePalette) -> DualPalettedRuns {
+        let shared = runs(in: code, tokens: tokens)
+        return DualPalettedRuns(
+            light: paletted(shared, palette: light, mergingAdjacent: false),
+            dark: paletted(shared, palette: dark, mergingAdjacent: false)
+        )
+    }
+
+    private static func paletted(_ runs: [HighlightRun], palette: ThemePalette, mergingAdjacent: Bool) -> PalettedRuns {
+        var colors = [palette.text.uppercased()]
+        var indices = [colors[0]: 0]
+        var result: [PalettedRun] = []
+
+        for run in runs {
+            let hex = (run.tokenType.map { palette.hex(for: $0) } ?? palette.text).uppercased()
+            let index: Int
+            if let existing = indices[hex] {
+                index = existing
+            } else {
+                index = colors.count
+                colors.append(hex)
+                indices[hex] = index
+            }
+            if mergingAdjacent, let last = result.last, last.colorIndex == index {
+                result[result.count - 1] = PalettedRun(colorIndex: index, text: last.text + run.text)
+            } else {
+                result.append(PalettedRun(colorIndex: index, text: run.text))
+            }
+        }
+        return PalettedRuns(background: palette.background, colors: colors, runs: result)
+    }
+
+    /// The token color covering the most non-whitespace text in the first `maxLines` lines, for
+    /// tinting preview chrome. Plain text and background colors don't count; falls back to the
+    /// palette accent when nothing else is colored.
+    public static func dominantColor(in code: String, tokens: [HighlightToken], palette: ThemePalette, maxLines: Int = 50) -> String {
+        let excluded: Set<String> = [palette.text.uppercased(), palette.background.uppercased()]
+        var weights: [String: Int] = [:]
+        var order: [String] = []
+
+        for line in lines(in: code, tokens: tokens, range: 1...max(maxLines, 1)) {
+            for run in line.runs {
+                guard let type = run.tokenType else { continue }
+                let hex = palette.hex(for: type).uppercased()
+                guard !excluded.contains(hex) else { continue }
+                let weight = run.text.filter { !$0.isWhitespace }.count
+                guard weight > 0 else { continue }
+                if weights[hex] == nil { order.append(hex) }
+                weights[hex, default: 0] += weight
+            }
+        }
+        // Ties go to the color seen first.
+        return order.max { (weights[$0] ?? 0) < (weights[$1] ?? 0) } ?? palette.accent.uppercased()
+    }
+
+    /// Compact JSON with sorted keys, stable enough to cache on disk or diff as a golden file.
+    /// Decode it back with `JSONDecoder` into `[HighlightedLine]`.
+    public static func jsonString(for lines: [HighlightedLine]) -> String {
+        let encoder = JSONEncoder()
+        encoder.outputFormatting = [.sortedKeys, .withoutEscapingSlashes]
+        guard let data = try? encoder.encode(lines) else { return "[]" }
+        return String(decoding: data, as: UTF8.self)
+    }
+}
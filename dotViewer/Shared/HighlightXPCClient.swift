@@ -19,7 +19,16 @@ public final class HighlightXPCClient: @unchecked Sendable {
         requestId: String,
         timeout: TimeInterval
     ) async -> Result<String, HighlightFallbackReason> {
-        await withCheckedContinuation { continuation in
+        if FileAttributes.isProbablyBinary(text: code) {
+            logger.info("Highlight skipped for binary content in request \(requestId, privacy: .public)")
+            return .failure(.binaryContent)
+        }
+        let cacheKey = HighlightResultCache.key(code: code, language: language, theme: theme, showLineNumbers: showLineNumbers)
+        if let cached = HighlightResultCache.shared.html(forKey: cacheKey) {
+            logger.info("Highlight served from cache for request \(requestId, privacy: .public)")
+            return .success(cached)
+        }
+        return await withCheckedContinuation { continuation in
             let state = CallbackState()
             let proxy = makeConnection().synchronousRemoteObjectProxyWithErrorHandler { _ in
                 state.finish {
@@ -55,6 +64,7 @@ public final class HighlightXPCClient: @unchecked Sendable {
                         return
                     }
                     self.logger.info("Highlight succeeded for request \(requestId, privacy: .public) size=\(html.count, privacy: .public)")
+                    HighlightResultCache.shared.store(html, forKey: cacheKey)
                     continuation.resume(returning: .success(html))
                 }
             }
@@ -121,6 +131,56 @@ public final class HighlightXPCClient: @unchecked Sendable {
         }
     }
 
+    /// `highlightTokens` with its wall-clock duration. Logged when performance logging is enabled.
+    public func highlightTokensTimed(
+        code: String,
+        language: String,
+        requestId: String,
+        timeout: TimeInterval
+    ) async -> Result<TimedHighlightTokens, HighlightFallbackReason> {
+        let clock = ContinuousClock()
+        let start = clock.now
+        let result = await highlightTokens(code: code, language: language, requestId: requestId, timeout: timeout)
+        let elapsed = clock.now - start
+
+        return result.map { tokens in
+            let timed = TimedHighlightTokens(tokens: tokens, elapsed: elapsed)
+            if SharedSettings.shared.performanceLoggingEnabled {
+                logger.info("Token highlight \(requestId, privacy: .public) language=\(language, privacy: .public) micros=\(timed.elapsedMicroseconds, privacy: .public) tokens=\(timed.tokenCount, privacy: .public)")
+            }
+            return timed
+        }
+    }
+
+    /// Reads `url` and highlights it with the language from its name, falling back to its shebang
+    /// and then plain text. Fails with `.unreadableFile` when the file can't be read and
+    /// `.binaryContent` when it isn't text.
+    public func highlightFile(
+        at url: URL,
+        theme: String,
+        showLineNumbers: Bool,
+        requestId: String,
+        timeout: TimeInterval,
+        maxBytes: Int = SharedSettings.shared.maxFileSizeBytes
+    ) async -> Result<String, HighlightFallbackReason> {
+        let fileInfo: FileInfo
+        do {
+            fileInfo = try FileInspector.loadFile(url: url, maxBytes: maxBytes, encoding: .utf8)
+        } catch {
+            logger.error("Highlight could not read \(url.lastPathComponent, privacy: .public): \(error.localizedDescription, privacy: .public)")
+            return .failure(.unreadableFile)
+        }
+        let language = FileTypeResolution.highlightLanguage(for: url, text: fileInfo.text) ?? "plaintext"
+        return await highlight(
+            code: fileInfo.text,
+            language: language,
+            theme: theme,
+            showLineNumbers: showLineNumbers,
+            requestId: requestId,
+            timeout: timeout
+        )
+    }
+
     public func cancel(requestId: String) {
         let proxy = makeConnection().synchronousRemoteObjectProxyWithErrorHandler { _ in } as? HighlightServiceProtocol
         proxy?.cancel(requestId: requestId)
@@ -0,0 +1,47 @@
+import Foundation
+
+/// Line-based spans for INI, `.conf` and `.properties` files, used when the tree-sitter INI grammar
+/// yields nothing (for example Java properties without any `[section]`). Section names are types,
+/// `#`/`;`/`!` comment lines are comments and keys are properties; values stay plain.
+public enum IniHighlighter {
+    public static func tokens(in code: String) -> [HighlightToken] {
+        let bytes = Array(code.utf8)
+        var tokens: [HighlightToken] = []
+        var lineStart = 0
+
+        // Lines end at LF, CRLF or a lone CR; all offsets are bytes into the whole document.
+        while lineStart <= bytes.count {
+            var lineEnd = lineStart
+            while lineEnd < bytes.count, bytes[lineEnd] != 0x0A, bytes[lineEnd] != 0x0D { lineEnd += 1 }
+            let isCRLF = lineEnd + 1 < bytes.count && bytes[lineEnd] == 0x0D && bytes[lineEnd + 1] == 0x0A
+            defer { lineStart = lineEnd + (isCRLF ? 2 : 1) }
+
+            guard let first = bytes[lineStart..<lineEnd].firstIndex(where: { $0 != 0x20 && $0 != 0x09 }) else { continue }
+            var last = lineEnd - 1
+            while last > first, bytes[last] == 0x20 || bytes[last] == 0x09 { last -= 1 }
+
+            switch bytes[first] {
+            case UInt8(ascii: "#"), UInt8(ascii: ";"), UInt8(ascii: "!"):
+                tokens.append(HighlightToken(s: first, e: last + 1, c: TokenType.comment.rawValue))
+            case UInt8(ascii: "["):
+                guard let close = bytes[first...last].lastIndex(of: UInt8(ascii: "]")) else { continue }
+                tokens.append(HighlightToken(s: first, e: first + 1, c: TokenType.punctuation.rawValue))
+                if close > first + 1 {
+                    tokens.append(HighlightToken(s: first + 1, e: close, c: TokenType.type.rawValue))
+                }
+                tokens.append(HighlightToken(s: close, e: close + 1, c: TokenType.punctuation.rawValue))
+            default:
+                guard let separator = bytes[first...last].firstIndex(where: { $0 == UInt8(ascii: "=") || $0 == UInt8(ascii: ":") }) else {
+                    continue
+                }
+                var keyEnd = separator
+                while keyEnd > first, bytes[keyEnd - 1] == 0x20 || bytes[keyEnd - 1] == 0x09 { keyEnd -= 1 }
+                if keyEnd > first {
+                    tokens.append(HighlightToken(s: first, e: keyEnd, c: TokenType.property.rawValue))
+                }
+                tokens.append(HighlightToken(s: separator, e: separator + 1, c: TokenType.punctuation.rawValue))
+            }
+        }
+        return tokens
+    }
+}
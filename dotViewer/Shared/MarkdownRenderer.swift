@@ -1,6 +1,42 @@
 import Foundation
 import AppKit
 
+public struct MarkdownCodeBlock: Equatable, Sendable {
+    /// Text after the opening fence, trimmed (e.g. `rust {.numberLines}`).
+    public let infoString: String
+    /// Highlight language resolved from the info string; `plaintext` when missing or unknown.
+    public let language: String
+    /// UTF-8 byte range of the block's content in the source, fences excluded.
+    public let codeRange: Range<Int>
+    public let code: String
+
+    public init(infoString: String, language: String, codeRange: Range<Int>, code: String) {
+        self.infoString = infoString
+        self.language = language
+        self.codeRange = codeRange
+        self.code = code
+    }
+}
+
+/// Leading `---` YAML or `+++` TOML metadata, split off so it can be highlighted in its own
+/// language while the rest is highlighted as Markdown.
+public struct MarkdownFrontMatter: Equatable, Sendable {
+    /// `yaml` or `toml`.
+    public let language: String
+    /// UTF-8 byte range of the metadata, fences excluded.
+    public let codeRange: Range<Int>
+    public let code: String
+    /// UTF-8 byte range of the Markdown after the closing fence.
+    public let bodyRange: Range<Int>
+
+    public init(language: String, codeRange: Range<Int>, code: String, bodyRange: Range<Int>) {
+        self.language = language
+        self.codeRange = codeRange
+        self.code = code
+        self.bodyRange = bodyRange
+    }
+}
+
 public enum MarkdownRenderer {
     public static func renderHTML(from markdown: String) -> String {
         convertMarkdownToHTML(markdown)
@@ -51,6 +87,104 @@ public enum MarkdownRenderer {
         return html
     }
 
+    /// Fenced code blocks in source order, including ones without a language. Fences are matched
+    /// the same way `renderHTML` matches them.
+    public static func fencedCodeBlocks(in markdown: String) -> [MarkdownCodeBlock] {
+        let lines = markdown.components(separatedBy: "\n")
+        var lineOffsets: [Int] = []
+        var offset = 0
+        for line in lines {
+            lineOffsets.append(offset)
+            offset += line.utf8.count + 1
+        }
+
+        var blocks: [MarkdownCodeBlock] = []
+        var i = 0
+        while i < lines.count {
+            let trimmed = lines[i].trimmingCharacters(in: .whitespaces)
+            guard trimmed.hasPrefix("```") || trimmed.hasPrefix("~~~") else {
+                i += 1
+                continue
+            }
+
+            let fence = trimmed.hasPrefix("```") ? "```" : "~~~"
+            let info = String(trimmed.dropFirst(fence.count)).trimmingCharacters(in: .whitespaces)
+            let first = i + 1
+            var end = first
+            while end < lines.count, !lines[end].trimmingCharacters(in: .whitespaces).hasPrefix(fence) {
+                end += 1
+            }
+
+            let codeLines = Array(lines[min(first, lines.count)..<end])
+            let start = first < lines.count ? lineOffsets[first] : offset - 1
+            let code = codeLines.joined(separator: "\n")
+            blocks.append(MarkdownCodeBlock(
+                infoString: info,
+                language: codeBlockLanguage(infoString: info) ?? "plaintext",
+                codeRange: start..<(start + code.utf8.count),
+                code: code
+            ))
+            i = end + 1
+        }
+        return blocks
+    }
+
+    /// Front matter at the very start of `markdown`: `---` … `---` (or `...`) as YAML, `+++` … `+++`
+    /// as TOML. Nil when the first line isn't a fence or the fence is never closed.
+    public static func frontMatter(in markdown: String) -> MarkdownFrontMatter? {
+        let lines = markdown.components(separatedBy: "\n")
+        guard let opening = lines.first?.trimmingCharacters(in: .whitespaces) else { return nil }
+
+        let language: String
+        let closers: Set<String>
+        switch opening {
+        case "---":
+            language = "yaml"
+            closers = ["---", "..."]
+        case "+++":
+            language = "toml"
+            closers = ["+++"]
+        default:
+            return nil
+        }
+        guard let close = lines.indices.dropFirst().first(where: { closers.contains(lines[$0].trimmingCharacters(in: .whitespaces)) }) else {
+            return nil
+        }
+
+        let start = lines[0].utf8.count + 1
+        let code = lines[1..<close].joined(separator: "\n")
+        let closeEnd = lines[0...close].reduce(0) { $0 + $1.utf8.count + 1 }
+        let total = markdown.utf8.count
+        return MarkdownFrontMatter(
+            language: language,
+            codeRange: start..<(start + code.utf8.count),
+            code: code,
+            bodyRange: min(closeEnd, total)..<total
+        )
+    }
+
+    /// Resolves the language word of a fence info string: `rust`, `{.rust}`, `language-rust`,
+    /// `rs,ignore` and `py title="x"` all work. Nil when nothing recognizable is there.
+    static func codeBlockLanguage(infoString: String) -> String? {
+        let word = infoString
+            .split(whereSeparator: { $0 == " " || $0 == "," || $0 == "{" || $0 == "}" || $0 == "=" })
+            .first
+            .map { String($0).trimmingCharacters(in: CharacterSet(charactersIn: ".")) }?
+            .lowercased()
+        guard var key = word, !key.isEmpty else { return nil }
+        if key.hasPrefix("language-") {
+            key.removeFirst("language-".count)
+        }
+
+        if let alias = FileTypeRegistry.resolveLanguageAlias(key) {
+            return alias
+        }
+        if HighlightLanguage.all.contains(where: { $0.id == key }) {
+            return key
+        }
+        return FileTypeRegistry.shared.highlightLanguage(for: key)
+    }
+
     private static func generateSlug(_ text: String) -> String {
         let stripped = text.replacingOccurrences(of: "<[^>]+>", with: "", options: .regularExpression)
         return stripped.lowercased()
@@ -81,7 +215,8 @@ public enum MarkdownRenderer {
             if trimmed.hasPrefix("```") || trimmed.hasPrefix("~~~") {
                 let fence = trimmed.hasPrefix("```") ? "```" : "~~~"
                 let lang = String(trimmed.dropFirst(fence.count)).trimmingCharacters(in: .whitespaces)
-                let langAttr = lang.isEmpty ? "" : " class=\"language-\(escapeHTML(lang))\""
+                let resolved = codeBlockLanguage(infoString: lang)
+                let langAttr = lang.isEmpty ? "" : " class=\"language-\(escapeHTML(resolved ?? lang))\""
                 let langLabel = lang.isEmpty ? "" : "<div class=\"code-lang\">\(escapeHTML(lang))</div>"
                 var codeLines: [String] = []
                 i += 1
@@ -358,7 +493,7 @@ public enum MarkdownRenderer {
 
     // MARK: - List parsing
 
-    private static func isListItem(_ line: String) -> Bool {
+    static func isListItem(_ line: String) -> Bool {
         let t = line.trimmingCharacters(in: .whitespaces)
         if isHorizontalRule(t) { return false }
         if t.range(of: #"^[-*+]\s+"#, options: .regularExpression) != nil { return true }
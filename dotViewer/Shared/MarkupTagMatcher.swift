@@ -0,0 +1,111 @@
+import Foundation
+
+public struct TagPair: Equatable, Sendable {
+    public let name: String
+    /// UTF-8 byte range of the opening tag, `<` through `>`.
+    public let open: Range<Int>
+    /// UTF-8 byte range of the closing tag, `</` through `>`.
+    public let close: Range<Int>
+
+    public init(name: String, open: Range<Int>, close: Range<Int>) {
+        self.name = name
+        self.open = open
+        self.close = close
+    }
+}
+
+/// Pairs opening and closing tags in HTML/XML for tag-matching. Comments, CDATA sections,
+/// declarations and processing instructions are skipped; self-closing tags (and HTML void
+/// elements) have no pair. Unbalanced tags are dropped rather than guessed at.
+public enum MarkupTagMatcher {
+    private static let htmlVoidElements: Set<String> = [
+        "area", "base", "br", "col", "embed", "hr", "img", "input",
+        "link", "meta", "param", "source", "track", "wbr",
+    ]
+
+    /// Pairs sorted by the position of their opening tag.
+    public static func pairs(in code: String, languageId: String?) -> [TagPair] {
+        let bytes = Array(code.utf8)
+        let isHTML = languageId?.lowercased() == "html"
+        var stack: [(name: String, open: Range<Int>)] = []
+        var pairs: [TagPair] = []
+        var index = 0
+
+        func hasPrefix(_ prefix: String, at position: Int) -> Bool {
+            let needle = Array(prefix.utf8)
+            return position + needle.count <= bytes.count && Array(bytes[position..<position + needle.count]) == needle
+        }
+        func skip(past terminator: String, from position: Int) -> Int {
+            var cursor = position
+            while cursor < bytes.count, !hasPrefix(terminator, at: cursor) {
+                cursor += 1
+            }
+            return min(cursor + terminator.utf8.count, bytes.count)
+        }
+        func isNameByte(_ byte: UInt8) -> Bool {
+            byte != UInt8(ascii: ">") && byte != UInt8(ascii: "/") && byte > 32
+        }
+        // Position just past the tag's `>`, honouring quoted attribute values, and whether it ended in `/>`.
+        func tagEnd(from position: Int) -> (end: Int, selfClosing: Bool)? {
+            var cursor = position
+            var quote: UInt8?
+            while cursor < bytes.count {
+                let byte = bytes[cursor]
+                if let open = quote {
+                    if byte == open { quote = nil }
+                } else if byte == UInt8(ascii: "\"") || byte == UInt8(ascii: "'") {
+                    quote = byte
+                } else if byte == UInt8(ascii: ">") {
+                    return (cursor + 1, cursor > position && bytes[cursor - 1] == UInt8(ascii: "/"))
+                }
+                cursor += 1
+            }
+            return nil
+        }
+
+        while index < bytes.count {
+            guard bytes[index] == UInt8(ascii: "<") else {
+                index += 1
+                continue
+            }
+
+            if hasPrefix("<!--", at: index) {
+                index = skip(past: "-->", from: index + 4)
+                continue
+            }
+            if hasPrefix("<![CDATA[", at: index) {
+                index = skip(past: "]]>", from: index + 9)
+                continue
+            }
+            if hasPrefix("<!", at: index) || hasPrefix("<?", at: index) {
+                index = skip(past: ">", from: index + 2)
+                continue
+            }
+
+            let isClosing = hasPrefix("</", at: index)
+            let nameStart = index + (isClosing ? 2 : 1)
+            var nameEnd = nameStart
+            while nameEnd < bytes.count, isNameByte(bytes[nameEnd]) {
+                nameEnd += 1
+            }
+            guard nameEnd > nameStart, let tag = tagEnd(from: nameEnd) else {
+                index += 1
+                continue
+            }
+            let name = String(decoding: bytes[nameStart..<nameEnd], as: UTF8.self)
+            let key = isHTML ? name.lowercased() : name
+
+            if isClosing {
+                if let match = stack.lastIndex(where: { $0.name == key }) {
+                    pairs.append(TagPair(name: name, open: stack[match].open, close: index..<tag.end))
+                    stack.removeSubrange(match...)
+                }
+            } else if !tag.selfClosing, !(isHTML && htmlVoidElements.contains(key)) {
+                stack.append((key, index..<tag.end))
+            }
+            index = tag.end
+        }
+
+        return pairs.sorted { $0.open.lowerBound < $1.open.lowerBound }
+    }
+}
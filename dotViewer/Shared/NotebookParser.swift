@@ -0,0 +1,64 @@
+import Foundation
+
+public enum NotebookParserError: Error, Equatable {
+    case invalidJSON
+    case missingCells
+}
+
+public struct NotebookCell: Equatable, Sendable {
+    /// `code`, `markdown` or `raw`, as stored in the notebook.
+    public let cellType: String
+    /// Highlight language for the cell: the kernel language for code cells, `markdown` for
+    /// markdown cells, `plaintext` otherwise.
+    public let language: String
+    public let source: String
+
+    public init(cellType: String, language: String, source: String) {
+        self.cellType = cellType
+        self.language = language
+        self.source = source
+    }
+}
+
+/// Splits a Jupyter `.ipynb` (nbformat 4) into cells so code cells can be highlighted in their
+/// kernel's language.
+public enum NotebookParser {
+    public static func cells(from data: Data) throws -> [NotebookCell] {
+        guard let root = try? JSONSerialization.jsonObject(with: data) as? [String: Any] else {
+            throw NotebookParserError.invalidJSON
+        }
+        guard let cells = root["cells"] as? [[String: Any]] else {
+            throw NotebookParserError.missingCells
+        }
+
+        let kernelLanguage = kernelLanguage(metadata: root["metadata"] as? [String: Any])
+        return cells.map { cell in
+            let cellType = cell["cell_type"] as? String ?? "raw"
+            let language: String
+            switch cellType {
+            case "code": language = kernelLanguage
+            case "markdown": language = "markdown"
+            default: language = "plaintext"
+            }
+            return NotebookCell(cellType: cellType, language: language, source: source(of: cell["source"]))
+        }
+    }
+
+    static func kernelLanguage(metadata: [String: Any]?) -> String {
+        let kernelspec = metadata?["kernelspec"] as? [String: Any]
+        let languageInfo = metadata?["language_info"] as? [String: Any]
+        let name = kernelspec?["language"] as? String ?? languageInfo?["name"] as? String ?? "python"
+        return HighlightLanguage.named(name)?.id ?? name.lowercased()
+    }
+
+    /// nbformat stores source either as one string or as a list of lines that keep their newlines.
+    private static func source(of value: Any?) -> String {
+        if let text = value as? String {
+            return text
+        }
+        if let lines = value as? [String] {
+            return lines.joined()
+        }
+        return ""
+    }
+}
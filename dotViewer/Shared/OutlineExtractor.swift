@@ -0,0 +1,69 @@
+import Foundation
+
+public enum OutlineItemKind: String, Sendable {
+    case function
+    case type
+    case heading
+}
+
+public struct OutlineItem: Equatable, Sendable {
+    /// 1-based display line number.
+    public let line: Int
+    public let kind: OutlineItemKind
+    /// Declared name, or heading text.
+    public let text: String
+
+    public init(line: Int, kind: OutlineItemKind, text: String) {
+        self.line = line
+        self.kind = kind
+        self.text = text
+    }
+}
+
+/// Lightweight structural outline (declarations and headings) found line by line, without
+/// running the highlighter. Declarations are recognized by their introducing keyword, so the
+/// same rules cover Swift, Rust, Go, Python, JavaScript/TypeScript, Kotlin and friends.
+public enum OutlineExtractor {
+    private static let declarationPattern = try? NSRegularExpression(pattern: """
+        ^\\s*(?:(?:pub(?:\\([a-z]+\\))?|public|private|fileprivate|internal|open|export|default|async|static|final|abstract|override|unsafe|const|extern|inline|data|sealed)\\s+)*\
+        (fn|func|fun|def|function|class|struct|enum|trait|interface|protocol|type|union|object|actor)\\s+\
+        ([A-Za-z_$][A-Za-z0-9_$]*)
+        """)
+    private static let functionKeywords: Set<String> = ["fn", "func", "fun", "def", "function"]
+    private static let markdownLanguages: Set<String> = ["markdown", "md", "mdx"]
+
+    public static func items(in text: String, languageId: String?) -> [OutlineItem] {
+        let isMarkdown = languageId.map { markdownLanguages.contains($0.lowercased()) } ?? false
+        var items: [OutlineItem] = []
+        var inFence = false
+
+        for (index, line) in TextLineUtilities.displayLines(in: text).enumerated() {
+            if isMarkdown {
+                let trimmed = line.trimmingCharacters(in: .whitespaces)
+                if trimmed.hasPrefix("```") || trimmed.hasPrefix("~~~") {
+                    inFence.toggle()
+                    continue
+                }
+                guard !inFence, trimmed.hasPrefix("#") else { continue }
+                let level = trimmed.prefix(while: { $0 == "#" }).count
+                let heading = trimmed.dropFirst(level)
+                guard level <= 6, heading.hasPrefix(" ") else { continue }
+                items.append(OutlineItem(line: index + 1, kind: .heading, text: heading.trimmingCharacters(in: .whitespaces)))
+                continue
+            }
+
+            let range = NSRange(line.startIndex..., in: line)
+            guard let match = declarationPattern?.firstMatch(in: line, range: range),
+                  let keywordRange = Range(match.range(at: 1), in: line),
+                  let nameRange = Range(match.range(at: 2), in: line)
+            else { continue }
+            let keyword = String(line[keywordRange])
+            items.append(OutlineItem(
+                line: index + 1,
+                kind: functionKeywords.contains(keyword) ? .function : .type,
+                text: String(line[nameRange])
+            ))
+        }
+        return items
+    }
+}
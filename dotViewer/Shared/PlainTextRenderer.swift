@@ -1,20 +1,40 @@
 import Foundation
 
 public enum PlainTextRenderer {
-    public static func render(code: String, showLineNumbers: Bool) -> String {
+    /// `startNumber` numbers the first line, so a partial file keeps its real line numbers.
+    public static func render(code: String, showLineNumbers: Bool, startNumber: Int = 1) -> String {
         let data = code.data(using: .utf8) ?? Data()
-        return TreeSitterFallbackRenderer.renderPlain(data: data, showLineNumbers: showLineNumbers)
+        return TreeSitterFallbackRenderer.renderPlain(data: data, showLineNumbers: showLineNumbers, startNumber: startNumber)
+    }
+
+    /// Highlighted code in the gutter layout (`.ln` cells colored with `--gutter`), built from
+    /// XPC tokens, with line numbers counting from `startNumber`.
+    public static func render(code: String, tokens: [HighlightToken], startNumber: Int = 1) -> String {
+        HighlightRuns.lines(in: code, tokens: tokens, range: 1...Int.max).map { line in
+            let html = line.runs.map { run in
+                let text = TreeSitterFallbackRenderer.escapeHTML(run.text)
+                guard let type = run.tokenType else { return text }
+                return "<span class=\"tok-\(type.rawValue)\">\(text)</span>"
+            }.joined()
+            return "<div class=\"line\"><span class=\"ln\">\(startNumber + line.number - 1)</span><span class=\"code-line\">\(html)</span></div>"
+        }.joined()
+    }
+
+    /// HTML-escapes `string` with control characters shown as their pictures, for Shared
+    /// renderers that build their own markup.
+    static func escapeHTML(_ string: String) -> String {
+        TreeSitterFallbackRenderer.escapeHTML(string)
     }
 }
 
 private enum TreeSitterFallbackRenderer {
-    static func renderPlain(data: Data, showLineNumbers: Bool) -> String {
+    static func renderPlain(data: Data, showLineNumbers: Bool, startNumber: Int) -> String {
         let text = String(decoding: data, as: UTF8.self)
         let lines = TextLineUtilities.lines(forDisplayFrom: text).map(escapeHTML)
 
         if showLineNumbers {
             return lines.enumerated().map { index, line in
-                let lineNumber = index + 1
+                let lineNumber = startNumber + index
                 return "<div class=\"line\"><span class=\"ln\">\(lineNumber)</span><span class=\"code-line\">\(line)</span></div>"
             }.joined()
         } else {
@@ -23,12 +43,25 @@ private enum TreeSitterFallbackRenderer {
         }
     }
 
+    /// Single pass over the UTF-8 bytes rather than one `replacingOccurrences` per entity, since
+    /// plain rendering is what the largest files go through.
     static func escapeHTML(_ string: String) -> String {
-        var escaped = string
-        escaped = escaped.replacingOccurrences(of: "&", with: "&amp;")
-        escaped = escaped.replacingOccurrences(of: "<", with: "&lt;")
-        escaped = escaped.replacingOccurrences(of: ">", with: "&gt;")
-        escaped = escaped.replacingOccurrences(of: "\"", with: "&quot;")
-        return escaped
+        var escaped: [UInt8] = []
+        escaped.reserveCapacity(string.utf8.count)
+        for byte in string.utf8 {
+            switch byte {
+            case UInt8(ascii: "&"): escaped.append(contentsOf: "&amp;".utf8)
+            case UInt8(ascii: "<"): escaped.append(contentsOf: "&lt;".utf8)
+            case UInt8(ascii: ">"): escaped.append(contentsOf: "&gt;".utf8)
+            case UInt8(ascii: "\""): escaped.append(contentsOf: "&quot;".utf8)
+            case 0x09, 0x0A, 0x0D: escaped.append(byte)
+            case 0x00..<0x20, 0x7F:
+                // Control Picture for the byte (U+2400 + byte, or U+2421 for DEL).
+                let picture = byte == 0x7F ? 0x21 : byte
+                escaped.append(contentsOf: [0xE2, 0x90, 0x80 + picture])
+            default: escaped.append(byte)
+            }
+        }
+        return String(decoding: escaped, as: UTF8.self)
     }
 }
@@ -73,6 +73,34 @@ public struct PreviewCacheKey: Hashable, Sendable {
     }
 }
 
+/// Whether a preview of a given size should be cached, aligned with the preview's truncation
+/// limit so the extension and settings agree on one rule.
+public enum PreviewCachePolicy: Equatable, Sendable {
+    case cache
+    /// Too large to be worth even a truncated entry.
+    case noCache
+    /// Cache only the preview's truncated prefix.
+    case truncateThenCache
+
+    public static let maxCachedLineCount = 50_000
+    /// Files beyond this multiple of the truncation limit skip the cache entirely.
+    public static let noCacheSizeMultiple = 20
+
+    public static func recommended(
+        byteCount: Int,
+        lineCount: Int,
+        maxBytes: Int = SharedSettings.shared.maxFileSizeBytes
+    ) -> PreviewCachePolicy {
+        if byteCount <= maxBytes, lineCount <= maxCachedLineCount {
+            return .cache
+        }
+        if byteCount <= maxBytes * noCacheSizeMultiple {
+            return .truncateThenCache
+        }
+        return .noCache
+    }
+}
+
 public struct PreviewCacheEntry: Codable, Sendable {
     public let createdAt: Date
     public let rawHTML: String
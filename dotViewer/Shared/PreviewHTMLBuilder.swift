@@ -38,6 +38,10 @@ public struct PreviewInfo {
     public let showSearchButton: Bool
     public let includeLineNumbersInCopy: Bool
     public let sourceDirectory: String
+    /// When set, every other token category is dimmed toward the background.
+    public let focusTokenType: TokenType?
+    /// Per-token font style tweaks; their colors are expected to be applied to the palette already.
+    public let styleOverrides: [ScopeOverride]
 
     public init(
         title: String,
@@ -76,7 +80,9 @@ public struct PreviewInfo {
         copyBehavior: String = "autoCopy",
         showSearchButton: Bool = false,
         includeLineNumbersInCopy: Bool = false,
-        sourceDirectory: String = ""
+        sourceDirectory: String = "",
+        focusTokenType: TokenType? = nil,
+        styleOverrides: [ScopeOverride] = []
     ) {
         self.title = title
         self.language = language
@@ -121,6 +127,8 @@ public struct PreviewInfo {
         self.showSearchButton = showSearchButton
         self.includeLineNumbersInCopy = includeLineNumbersInCopy
         self.sourceDirectory = sourceDirectory
+        self.focusTokenType = focusTokenType
+        self.styleOverrides = styleOverrides
     }
 }
 
@@ -325,12 +333,13 @@ public enum PreviewHTMLBuilder {
               --namespace: \(palette.namespace);
               --parameter: \(palette.parameter);
               --heading: \(palette.isDark ? palette.type : palette.text);
-              --gutter: \(palette.isDark ? "#3B3F51" : "#C0C4CC");
+              --gutter: \(palette.gutter);
               --header: \(palette.isDark ? "#1F232B" : "#F2F3F5");
               --surface: \(palette.isDark ? "#1E222A" : "#F5F7FA");
               --surface-strong: \(palette.isDark ? "#2B303B" : "#E9EDF2");
               --border: \(palette.isDark ? "rgba(255,255,255,0.08)" : "rgba(0,0,0,0.08)");
               --link: \(palette.accent);
+              --selection: \(palette.selectionBackground);
             """
         }
 
@@ -530,7 +539,8 @@ public enum PreviewHTMLBuilder {
         }
 
         /* Token classes generated from TokenType enum — see tokenCSSRules() */
-        \(tokenCSSRules())
+        \(tokenCSSRules(styleOverrides: info.styleOverrides))
+        \(focusTokenCSSRules(focus: info.focusTokenType))
 
         /* Markdown RAW mode: size/weight differentiation */
         .code-view[data-language="markdown"] .tok-keyword {
@@ -701,8 +711,9 @@ public enum PreviewHTMLBuilder {
           margin-top: 0 !important;
         }
 
+        .code-view ::selection,
         .rendered-view ::selection {
-          background: rgba(0, 122, 255, 0.2);
+          background: var(--selection);
         }
 
         /* Typora-inspired Typography */
@@ -1294,6 +1305,24 @@ public enum PreviewHTMLBuilder {
           font-weight: 600;
         }
 
+        /* MARK: - Diff */
+        .line.diff-add {
+          background: rgba(46, 160, 67, 0.15);
+        }
+        .line.diff-del {
+          background: rgba(248, 81, 73, 0.15);
+        }
+        .line.diff-hunk {
+          background: var(--surface);
+          color: var(--accent);
+        }
+        .line.diff-file {
+          font-weight: 600;
+        }
+        .diff-marker {
+          opacity: 0.7;
+        }
+
         /* MARK: - Print */
         @media print {
           body {
@@ -2437,25 +2466,47 @@ public enum PreviewHTMLBuilder {
 
     /// Generates CSS token class rules from the TokenType enum.
     /// Using the enum ensures new token types produce a compiler error in hex(for:)
-    /// and must be added here for CSS coverage.
-    static func tokenCSSRules() -> String {
+    /// and must be added here for CSS coverage. A token with a font style override gets its
+    /// weight, slant and underline set explicitly instead of the built-in extras.
+    static func tokenCSSRules(styleOverrides: [ScopeOverride] = []) -> String {
         // Extra per-token CSS (beyond just color)
         let extraStyles: [TokenType: String] = [
             .builtin: " font-style: italic;",
             .parameter: " font-style: italic;",
         ]
         return TokenType.allCases.map { token in
-            let varName: String
-            switch token {
-            case .constant:   varName = "--number"
-            case .identifier: varName = "--text"
-            default:          varName = "--\(token.rawValue)"
+            let extra: String
+            if styleOverrides.contains(where: { $0.fontStyle != nil && TextMateThemeParser.tokenType(forScope: $0.scope) == token }) {
+                let flags = ScopeOverride.styleFlags(for: token, overrides: styleOverrides)
+                extra = " font-weight: \(flags.isBold ? "bold" : "normal");"
+                    + " font-style: \(flags.isItalic ? "italic" : "normal");"
+                    + " text-decoration: \(flags.isUnderline ? "underline" : "none");"
+            } else {
+                extra = extraStyles[token] ?? ""
             }
-            let extra = extraStyles[token] ?? ""
-            return ".tok-\(token.rawValue) { color: var(\(varName));\(extra) }"
+            return ".tok-\(token.rawValue) { color: var(\(cssVariable(for: token)));\(extra) }"
         }.joined(separator: "\n        ")
     }
 
+    /// Dims untokenized text and every token category except `focus` toward the background.
+    static func focusTokenCSSRules(focus: TokenType?) -> String {
+        guard let focus else { return "" }
+        let dimmed = { (variable: String) in "color-mix(in srgb, var(\(variable)) 35%, var(--bg))" }
+        let baseRule = ".code-view .code-line, .code-view pre.code { color: \(dimmed("--text")); }"
+        let tokenRules = TokenType.allCases.filter { $0 != focus }.map { token in
+            ".code-view .tok-\(token.rawValue) { color: \(dimmed(cssVariable(for: token))); }"
+        }
+        return ([baseRule] + tokenRules).joined(separator: "\n        ")
+    }
+
+    private static func cssVariable(for token: TokenType) -> String {
+        switch token {
+        case .constant:   return "--number"
+        case .identifier: return "--text"
+        default:          return "--\(token.rawValue)"
+        }
+    }
+
     private static func escapeHTML(_ string: String) -> String {
         var escaped = string
         escaped = escaped.replacingOccurrences(of: "&", with: "&amp;")
@@ -0,0 +1,79 @@
+import Foundation
+
+/// 8-bit sRGB color parsed from the `#RRGGBB` strings used by `ThemePalette`.
+public struct RGBColor: Hashable, Sendable {
+    public let red: UInt8
+    public let green: UInt8
+    public let blue: UInt8
+
+    public init(red: UInt8, green: UInt8, blue: UInt8) {
+        self.red = red
+        self.green = green
+        self.blue = blue
+    }
+
+    public init?(hex: String) {
+        var sanitized = hex.trimmingCharacters(in: .whitespacesAndNewlines)
+        if sanitized.hasPrefix("#") { sanitized.removeFirst() }
+        guard sanitized.count == 6,
+              sanitized.allSatisfy(\.isHexDigit),
+              let value = UInt32(sanitized, radix: 16)
+        else { return nil }
+        red = UInt8((value >> 16) & 0xFF)
+        green = UInt8((value >> 8) & 0xFF)
+        blue = UInt8(value & 0xFF)
+    }
+
+    public var hex: String {
+        String(format: "#%02X%02X%02X", Int(red), Int(green), Int(blue))
+    }
+
+    /// WCAG 2.x relative luminance in `0...1`.
+    public var relativeLuminance: Double {
+        func linear(_ value: UInt8) -> Double {
+            let channel = Double(value) / 255
+            return channel <= 0.03928 ? channel / 12.92 : pow((channel + 0.055) / 1.055, 2.4)
+        }
+        return 0.2126 * linear(red) + 0.7152 * linear(green) + 0.0722 * linear(blue)
+    }
+
+    /// True when white text would contrast better than black text on this color.
+    public var isDark: Bool {
+        relativeLuminance < 0.179
+    }
+
+    /// WCAG contrast ratio in `1...21`.
+    public func contrastRatio(with other: RGBColor) -> Double {
+        let lighter = max(relativeLuminance, other.relativeLuminance)
+        let darker = min(relativeLuminance, other.relativeLuminance)
+        return (lighter + 0.05) / (darker + 0.05)
+    }
+
+    /// Linear blend toward `other`; `fraction` 0 is self, 1 is `other`.
+    public func mixed(with other: RGBColor, fraction: Double) -> RGBColor {
+        let t = min(max(fraction, 0), 1)
+        func channel(_ a: UInt8, _ b: UInt8) -> UInt8 {
+            UInt8((Double(a) + (Double(b) - Double(a)) * t).rounded())
+        }
+        return RGBColor(red: channel(red, other.red), green: channel(green, other.green), blue: channel(blue, other.blue))
+    }
+
+    /// The smallest shift toward white (on dark backgrounds) or black (on light ones) that reaches
+    /// `minimumRatio` against `background`. Colors that already comply are returned unchanged.
+    public func adjustedForContrast(against background: RGBColor, minimumRatio: Double) -> RGBColor {
+        guard contrastRatio(with: background) < minimumRatio else { return self }
+        let target = background.isDark ? RGBColor(red: 255, green: 255, blue: 255) : RGBColor(red: 0, green: 0, blue: 0)
+        guard target.contrastRatio(with: background) > minimumRatio else { return target }
+
+        var low = 0.0, high = 1.0
+        for _ in 0..<16 {
+            let middle = (low + high) / 2
+            if mixed(with: target, fraction: middle).contrastRatio(with: background) >= minimumRatio {
+                high = middle
+            } else {
+                low = middle
+            }
+        }
+        return mixed(with: target, fraction: high)
+    }
+}
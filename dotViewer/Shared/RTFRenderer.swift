@@ -0,0 +1,82 @@
+import Foundation
+
+/// Builds an RTF document from highlight tokens for rich-text pasteboard / drag-and-drop use.
+public enum RTFRenderer {
+    public static func render(
+        code: String,
+        tokens: [HighlightToken],
+        palette: ThemePalette,
+        fontName: String,
+        fontSize: Int,
+        styleOverrides: [ScopeOverride] = []
+    ) -> String {
+        let runs = HighlightRuns.runs(in: code, tokens: tokens)
+
+        var colors: [String] = [palette.text.uppercased()]
+        func colorIndex(for hex: String) -> Int {
+            let key = hex.uppercased()
+            if let index = colors.firstIndex(of: key) {
+                return index + 1
+            }
+            colors.append(key)
+            return colors.count
+        }
+
+        var body = ""
+        for run in runs {
+            let hex = run.tokenType.map { palette.hex(for: $0) } ?? palette.text
+            let flags = run.tokenType.map { ScopeOverride.styleFlags(for: $0, overrides: styleOverrides) } ?? .plain
+            var group = "{\\cf\(colorIndex(for: hex))"
+            if flags.isBold { group += "\\b" }
+            if flags.isItalic { group += "\\i" }
+            if flags.isUnderline { group += "\\ul" }
+            body += group + " " + escape(run.text) + "}"
+        }
+
+        let colorTable = colors.compactMap(RGBColor.init(hex:)).map { color in
+            "\\red\(color.red)\\green\(color.green)\\blue\(color.blue);"
+        }.joined()
+
+        return "{\\rtf1\\ansi\\ansicpg1252\\deff0"
+            + "{\\fonttbl{\\f0\\fmodern \(escape(fontName));}}"
+            + "{\\colortbl;\(colorTable)}"
+            + "\\f0\\fs\(max(fontSize, 1) * 2) "
+            + body
+            + "}"
+    }
+
+    /// Escapes RTF control characters; line breaks (`\n`, `\r\n` or a lone `\r`) become `\line`,
+    /// tabs `\tab`, and anything outside 7-bit ASCII a `\uN?` escape per UTF-16 code unit (signed,
+    /// as RTF requires).
+    public static func escape(_ text: String) -> String {
+        var escaped = ""
+        let scalars = Array(TextLineUtilities.visibleControlCharacters(text).unicodeScalars)
+        for (index, scalar) in scalars.enumerated() {
+            switch scalar {
+            case "\\":
+                escaped += "\\\\"
+            case "{":
+                escaped += "\\{"
+            case "}":
+                escaped += "\\}"
+            case "\n":
+                escaped += "\\line "
+            case "\r":
+                // CRLF is written once, at its LF.
+                if index + 1 < scalars.count, scalars[index + 1] == "\n" { continue }
+                escaped += "\\line "
+            case "\t":
+                escaped += "\\tab "
+            default:
+                if scalar.isASCII {
+                    escaped.unicodeScalars.append(scalar)
+                } else {
+                    for unit in String(scalar).utf16 {
+                        escaped += "\\u\(Int16(bitPattern: unit))?"
+                    }
+                }
+            }
+        }
+        return escaped
+    }
+}
@@ -0,0 +1,129 @@
+import Foundation
+
+/// Overlays find-in-preview matches on highlighted code HTML. Matches are found in the visible
+/// text, so a query can span several token spans; the highlight is split at every tag boundary so
+/// the surrounding `tok-*` markup stays well-formed and keeps its colors.
+public enum SearchMatchHighlighter {
+    public static let matchClass = "search-highlight"
+
+    private enum Piece {
+        case tag(String)
+        case character(raw: String, value: Character)
+    }
+
+    /// Character-offset ranges of `query` in `text`. Overlapping or touching matches are merged.
+    /// Foundation can report a match that starts or ends inside a grapheme cluster (e.g. a query
+    /// ending in a bare combining mark); such matches are widened to whole characters.
+    public static func matchRanges(in text: String, query: String, caseSensitive: Bool) -> [Range<Int>] {
+        guard !query.isEmpty, !text.isEmpty else { return [] }
+        let options: String.CompareOptions = caseSensitive ? [] : [.caseInsensitive]
+
+        var ranges: [Range<Int>] = []
+        var searchStart = text.startIndex
+        var anchor = text.startIndex
+        var anchorOffset = 0
+
+        while searchStart < text.endIndex,
+              let match = text.range(of: query, options: options, range: searchStart..<text.endIndex) {
+            let found = text.rangeOfComposedCharacterSequences(for: match)
+            let lower = anchorOffset + text.distance(from: anchor, to: found.lowerBound)
+            let upper = lower + text.distance(from: found.lowerBound, to: found.upperBound)
+            anchor = found.lowerBound
+            anchorOffset = lower
+
+            if let last = ranges.last, lower <= last.upperBound {
+                ranges[ranges.count - 1] = last.lowerBound..<max(last.upperBound, upper)
+            } else {
+                ranges.append(lower..<upper)
+            }
+            searchStart = text.index(after: found.lowerBound)
+        }
+        return ranges
+    }
+
+    public static func highlight(html: String, query: String, caseSensitive: Bool) -> String {
+        let pieces = parse(html)
+        let text = String(pieces.compactMap { piece -> Character? in
+            if case .character(_, let value) = piece { return value }
+            return nil
+        })
+        let ranges = matchRanges(in: text, query: query, caseSensitive: caseSensitive)
+        guard !ranges.isEmpty else { return html }
+
+        var output = ""
+        var offset = 0
+        var rangeIndex = 0
+        var isOpen = false
+
+        for piece in pieces {
+            switch piece {
+            case .tag(let tag):
+                if isOpen {
+                    output += "</span>"
+                    isOpen = false
+                }
+                output += tag
+            case .character(let raw, _):
+                while rangeIndex < ranges.count, ranges[rangeIndex].upperBound <= offset {
+                    rangeIndex += 1
+                }
+                let inMatch = rangeIndex < ranges.count && ranges[rangeIndex].contains(offset)
+                if inMatch && !isOpen {
+                    output += "<span class=\"\(matchClass)\">"
+                    isOpen = true
+                } else if !inMatch && isOpen {
+                    output += "</span>"
+                    isOpen = false
+                }
+                output += raw
+                offset += 1
+            }
+        }
+        if isOpen {
+            output += "</span>"
+        }
+        return output
+    }
+
+    /// Splits HTML into tags and visible characters, keeping entities intact but decoded for matching.
+    private static func parse(_ html: String) -> [Piece] {
+        var pieces: [Piece] = []
+        var index = html.startIndex
+
+        while index < html.endIndex {
+            let character = html[index]
+            if character == "<", let close = html[index...].firstIndex(of: ">") {
+                let next = html.index(after: close)
+                pieces.append(.tag(String(html[index..<next])))
+                index = next
+            } else if character == "&",
+                      let semicolon = html[index...].prefix(10).firstIndex(of: ";"),
+                      let decoded = decodeEntity(String(html[index...semicolon])) {
+                let next = html.index(after: semicolon)
+                pieces.append(.character(raw: String(html[index..<next]), value: decoded))
+                index = next
+            } else {
+                pieces.append(.character(raw: String(character), value: character))
+                index = html.index(after: index)
+            }
+        }
+        return pieces
+    }
+
+    private static func decodeEntity(_ entity: String) -> Character? {
+        switch entity {
+        case "&amp;":
+            return "&"
+        case "&lt;":
+            return "<"
+        case "&gt;":
+            return ">"
+        case "&quot;":
+            return "\""
+        case "&#39;", "&apos;":
+            return "'"
+        default:
+            return nil
+        }
+    }
+}
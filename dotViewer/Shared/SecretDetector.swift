@@ -0,0 +1,126 @@
+import Foundation
+
+public enum SecretKind: String, Sendable {
+    case awsAccessKey
+    case assignedCredential
+    case privateKeyBlock
+    case highEntropyToken
+}
+
+public struct SecretMatch: Equatable, Sendable {
+    /// 1-based display line number.
+    public let line: Int
+    /// 0-based character columns of the secret within the line.
+    public let columns: Range<Int>
+    public let kind: SecretKind
+
+    public init(line: Int, columns: Range<Int>, kind: SecretKind) {
+        self.line = line
+        self.columns = columns
+        self.kind = kind
+    }
+}
+
+/// Content-based counterpart to `SensitiveFileDetector`: finds likely credentials inside a file
+/// so a preview can mask them.
+public enum SecretDetector {
+    public static let maskCharacter: Character = "•"
+
+    private static let awsKeyPattern = try? NSRegularExpression(pattern: "\\b(?:AKIA|ASIA)[0-9A-Z]{16}\\b")
+    private static let assignmentPattern = try? NSRegularExpression(
+        pattern: "(?i)[a-z0-9_.-]*(?:api[_-]?key|secret|token|passw(?:or)?d)[a-z0-9_.-]*[\"']?\\s*[:=]\\s*[\"']?([^\\s\"',;()]{8,})(?=[\"'\\s,;]|$)"
+    )
+    private static let tokenPattern = try? NSRegularExpression(pattern: "[A-Za-z0-9+/_=-]{32,}")
+    private static let entropyThreshold = 4.0
+
+    public static func detect(in text: String) -> [SecretMatch] {
+        var matches: [SecretMatch] = []
+        var inPrivateKey = false
+
+        for (index, line) in TextLineUtilities.displayLines(in: text).enumerated() {
+            let lineNumber = index + 1
+
+            if line.contains("-----BEGIN"), line.contains("PRIVATE KEY-----") {
+                inPrivateKey = true
+            }
+            if inPrivateKey {
+                matches.append(SecretMatch(line: lineNumber, columns: 0..<line.count, kind: .privateKeyBlock))
+                if line.contains("-----END") {
+                    inPrivateKey = false
+                }
+                continue
+            }
+
+            var lineMatches: [SecretMatch] = []
+            func record(_ columns: Range<Int>, _ kind: SecretKind) {
+                guard !lineMatches.contains(where: { $0.columns.overlaps(columns) }) else { return }
+                lineMatches.append(SecretMatch(line: lineNumber, columns: columns, kind: kind))
+            }
+
+            for columns in ranges(of: awsKeyPattern, in: line, group: 0) {
+                record(columns, .awsAccessKey)
+            }
+            for columns in ranges(of: assignmentPattern, in: line, group: 1) {
+                record(columns, .assignedCredential)
+            }
+            for columns in ranges(of: tokenPattern, in: line, group: 0) {
+                let token = substring(of: line, columns: columns)
+                if token.contains(where: \.isNumber), token.contains(where: \.isLetter),
+                   shannonEntropy(of: token) >= entropyThreshold {
+                    record(columns, .highEntropyToken)
+                }
+            }
+
+            matches += lineMatches.sorted { $0.columns.lowerBound < $1.columns.lowerBound }
+        }
+        return matches
+    }
+
+    /// Replaces every detected secret with mask characters, keeping line lengths so columns and
+    /// line numbers in the preview stay put.
+    public static func redact(_ text: String, matches: [SecretMatch]? = nil) -> String {
+        let matches = matches ?? detect(in: text)
+        guard !matches.isEmpty else { return text }
+
+        var lines = TextLineUtilities.displayLines(in: text)
+        for match in matches where match.line >= 1 && match.line <= lines.count {
+            var characters = Array(lines[match.line - 1])
+            let upper = min(match.columns.upperBound, characters.count)
+            guard match.columns.lowerBound < upper else { continue }
+            for column in match.columns.lowerBound..<upper {
+                characters[column] = maskCharacter
+            }
+            lines[match.line - 1] = String(characters)
+        }
+        return lines.joined(separator: "\n") + (TextIndentation.hasFinalNewline(text) ? "\n" : "")
+    }
+
+    static func shannonEntropy(of token: String) -> Double {
+        guard !token.isEmpty else { return 0 }
+        var counts: [Character: Int] = [:]
+        for character in token {
+            counts[character, default: 0] += 1
+        }
+        let length = Double(token.count)
+        return counts.values.reduce(0) { total, count in
+            let probability = Double(count) / length
+            return total - probability * log2(probability)
+        }
+    }
+
+    private static func ranges(of regex: NSRegularExpression?, in line: String, group: Int) -> [Range<Int>] {
+        guard let regex else { return [] }
+        let searchRange = NSRange(line.startIndex..., in: line)
+        return regex.matches(in: line, range: searchRange).compactMap { result in
+            guard let range = Range(result.range(at: group), in: line) else { return nil }
+            let lower = line.distance(from: line.startIndex, to: range.lowerBound)
+            return lower..<(lower + line.distance(from: range.lowerBound, to: range.upperBound))
+        }
+    }
+
+    private static func substring(of line: String, columns: Range<Int>) -> Substring {
+        let start = line.index(line.startIndex, offsetBy: columns.lowerBound)
+        let end = line.index(start, offsetBy: columns.count)
+        return line[start..<end]
+    }
+}
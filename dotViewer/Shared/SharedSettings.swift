@@ -139,6 +139,11 @@ public final class SharedSettings: @unchecked Sendable {
         }
     }
 
+    public var thumbnailCollapseBlankLines: Bool {
+        get { lock.withLock { defaults.object(forKey: "thumbnailCollapseBlankLines") as? Bool ?? false } }
+        set { lock.withLock { defaults.set(newValue, forKey: "thumbnailCollapseBlankLines") } }
+    }
+
     public var showTruncationWarning: Bool {
         get { lock.withLock { defaults.object(forKey: "showTruncationWarning") as? Bool ?? true } }
         set { lock.withLock { defaults.set(newValue, forKey: "showTruncationWarning") } }
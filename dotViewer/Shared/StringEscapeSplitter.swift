@@ -0,0 +1,178 @@
+import Foundation
+
+/// Splits string spans so escape sequences inside them (`\n`, `\t`, `\xFF`, `\u{1F600}`) become
+/// their own spans. Grammar-provided escape captures are used when a string contains any;
+/// grammars without an escape query fall back to scanning the string's bytes, except in raw
+/// literals (`r"…"`, `@"…"`, and the language's `rawQuotes`) where backslashes are literal.
+public enum StringEscapeSplitter {
+    /// Quote bytes that open raw literals in `language`: shell, YAML and TOML single quotes and
+    /// Go backticks.
+    public static func rawQuotes(forLanguage language: String) -> Set<UInt8> {
+        switch language.lowercased() {
+        case "bash", "sh", "zsh", "fish", "yaml", "toml":
+            return [UInt8(ascii: "'")]
+        case "go":
+            return [UInt8(ascii: "`")]
+        default:
+            return []
+        }
+    }
+
+    /// `spans` carry capture names in `c`; the predicates classify those names. Pass
+    /// `scanBytes: false` when the grammar has escape captures of its own, so strings it left
+    /// unsplit stay whole. The result is sorted by start, longest first, like the highlighter's
+    /// capture list.
+    public static func split(
+        _ spans: [HighlightToken],
+        bytes: [UInt8],
+        isString: (String) -> Bool,
+        isEscape: (String) -> Bool,
+        scanBytes: Bool = true,
+        rawQuotes: Set<UInt8> = []
+    ) -> [HighlightToken] {
+        let grammarEscapes = spans.filter { isEscape($0.c) }.sorted { $0.s < $1.s }
+        var consumedEscapes = Set<Int>()
+        var result: [HighlightToken] = []
+
+        for span in spans where !isEscape(span.c) {
+            guard isString(span.c) else {
+                result.append(span)
+                continue
+            }
+
+            var escapes: [HighlightToken] = []
+            var index = firstIndex(in: grammarEscapes, startingAtOrAfter: span.s)
+            while index < grammarEscapes.count, grammarEscapes[index].s < span.e {
+                if grammarEscapes[index].e <= span.e {
+                    escapes.append(grammarEscapes[index])
+                    consumedEscapes.insert(index)
+                }
+                index += 1
+            }
+            if escapes.isEmpty, scanBytes, !isRawLiteral(bytes, at: span.s, rawQuotes: rawQuotes) {
+                escapes = escapeRanges(in: bytes, within: span.s..<span.e).map {
+                    HighlightToken(s: $0.lowerBound, e: $0.upperBound, c: "escape")
+                }
+            }
+            guard !escapes.isEmpty else {
+                result.append(span)
+                continue
+            }
+
+            var cursor = span.s
+            for escape in escapes where escape.s >= cursor {
+                if escape.s > cursor {
+                    result.append(HighlightToken(s: cursor, e: escape.s, c: span.c))
+                }
+                result.append(escape)
+                cursor = escape.e
+            }
+            if cursor < span.e {
+                result.append(HighlightToken(s: cursor, e: span.e, c: span.c))
+            }
+        }
+
+        // Escapes outside every string span keep their own capture.
+        for (index, escape) in grammarEscapes.enumerated() where !consumedEscapes.contains(index) {
+            result.append(escape)
+        }
+
+        return result.sorted { lhs, rhs in
+            lhs.s == rhs.s ? lhs.e > rhs.e : lhs.s < rhs.s
+        }
+    }
+
+    /// Whether the literal starting at `start` is raw: a letter prefix containing `r`/`R` before
+    /// its quote or `#` (Rust, Python, C++), a C# verbatim `@"`, or one of `rawQuotes`.
+    public static func isRawLiteral(_ bytes: [UInt8], at start: Int, rawQuotes: Set<UInt8> = []) -> Bool {
+        guard start >= 0, start < bytes.count else { return false }
+        if rawQuotes.contains(bytes[start]) {
+            return true
+        }
+        var index = start
+        var sawRawPrefix = false
+        var sawVerbatim = false
+        while index < bytes.count, index - start < 3 {
+            let byte = bytes[index]
+            if byte == UInt8(ascii: "r") || byte == UInt8(ascii: "R") {
+                sawRawPrefix = true
+            } else if byte == UInt8(ascii: "@") {
+                sawVerbatim = true
+            } else if !(byte == UInt8(ascii: "$") || (65...90).contains(byte) || (97...122).contains(byte)) {
+                break
+            }
+            index += 1
+        }
+        guard index > start, index < bytes.count else { return false }
+        let quote = bytes[index]
+        if sawVerbatim {
+            return quote == UInt8(ascii: "\"")
+        }
+        return sawRawPrefix && [UInt8(ascii: "\""), UInt8(ascii: "'"), UInt8(ascii: "#")].contains(quote)
+    }
+
+    /// Index of the first span in `sorted` whose start is at least `offset`.
+    private static func firstIndex(in sorted: [HighlightToken], startingAtOrAfter offset: Int) -> Int {
+        var low = 0
+        var high = sorted.count
+        while low < high {
+            let mid = (low + high) / 2
+            if sorted[mid].s < offset {
+                low = mid + 1
+            } else {
+                high = mid
+            }
+        }
+        return low
+    }
+
+    /// Byte ranges of backslash escape sequences within `range`.
+    public static func escapeRanges(in bytes: [UInt8], within range: Range<Int>) -> [Range<Int>] {
+        let end = min(range.upperBound, bytes.count)
+        var ranges: [Range<Int>] = []
+        var index = max(range.lowerBound, 0)
+
+        func isHexDigit(_ byte: UInt8) -> Bool {
+            (48...57).contains(byte) || (65...70).contains(byte) || (97...102).contains(byte)
+        }
+        func advance(from start: Int, whileMatching predicate: (UInt8) -> Bool, limit: Int) -> Int {
+            var position = start
+            while position < end, position - start < limit, predicate(bytes[position]) {
+                position += 1
+            }
+            return position
+        }
+
+        while index < end - 1 {
+            guard bytes[index] == 92 else { // backslash
+                index += 1
+                continue
+            }
+
+            let start = index
+            let kind = bytes[index + 1]
+            var stop = index + 2
+            switch kind {
+            case UInt8(ascii: "x"):
+                stop = advance(from: stop, whileMatching: isHexDigit, limit: 2)
+            case UInt8(ascii: "u"):
+                if stop < end, bytes[stop] == UInt8(ascii: "{") {
+                    let close = advance(from: stop + 1, whileMatching: isHexDigit, limit: 6)
+                    stop = close < end && bytes[close] == UInt8(ascii: "}") ? close + 1 : close
+                } else {
+                    stop = advance(from: stop, whileMatching: isHexDigit, limit: 4)
+                }
+            case UInt8(ascii: "U"):
+                stop = advance(from: stop, whileMatching: isHexDigit, limit: 8)
+            case UInt8(ascii: "0")...UInt8(ascii: "7"):
+                stop = advance(from: index + 1, whileMatching: { (48...55).contains($0) }, limit: 3)
+            default:
+                // Keep a multi-byte escaped character whole.
+                stop = advance(from: stop, whileMatching: { $0 & 0xC0 == 0x80 }, limit: 3)
+            }
+            ranges.append(start..<stop)
+            index = stop
+        }
+        return ranges
+    }
+}
@@ -0,0 +1,80 @@
+import Foundation
+
+/// EditorConfig-like formatting rules. `nil` fields are not enforced.
+public struct StylePolicy: Equatable, Sendable {
+    public var indentStyle: IndentStyle?
+    public var indentSize: Int?
+    public var requireFinalNewline: Bool
+    public var trimTrailingWhitespace: Bool
+
+    public init(
+        indentStyle: IndentStyle? = nil,
+        indentSize: Int? = nil,
+        requireFinalNewline: Bool = false,
+        trimTrailingWhitespace: Bool = false
+    ) {
+        self.indentStyle = indentStyle
+        self.indentSize = indentSize
+        self.requireFinalNewline = requireFinalNewline
+        self.trimTrailingWhitespace = trimTrailingWhitespace
+    }
+}
+
+public enum StyleViolationKind: String, Sendable {
+    case wrongIndentStyle
+    case wrongIndentSize
+    case trailingWhitespace
+    case missingFinalNewline
+}
+
+public struct StyleViolation: Equatable, Sendable {
+    /// 1-based display line number.
+    public let line: Int
+    public let kind: StyleViolationKind
+
+    public init(line: Int, kind: StyleViolationKind) {
+        self.line = line
+        self.kind = kind
+    }
+}
+
+public enum StylePolicyChecker {
+    /// Reports lines that break `policy`. When the policy leaves `indentStyle` unset, lines are
+    /// checked against the document's own dominant style so mixed indentation still surfaces.
+    public static func violations(in text: String, policy: StylePolicy) -> [StyleViolation] {
+        let lines = TextLineUtilities.displayLines(in: text)
+        let expectedStyle = policy.indentStyle ?? TextIndentation.detect(in: text)?.style
+        var violations: [StyleViolation] = []
+
+        for (index, line) in lines.enumerated() {
+            let lineNumber = index + 1
+
+            if !TextIndentation.isBlank(line) {
+                let leading = TextIndentation.leadingWhitespace(of: line)
+                switch expectedStyle {
+                case .some(.spaces) where leading.contains("\t"):
+                    violations.append(StyleViolation(line: lineNumber, kind: .wrongIndentStyle))
+                case .some(.tabs) where leading.first == " ":
+                    violations.append(StyleViolation(line: lineNumber, kind: .wrongIndentStyle))
+                default:
+                    if expectedStyle != .tabs,
+                       let size = policy.indentSize, size > 0,
+                       !leading.contains("\t"),
+                       leading.count % size != 0 {
+                        violations.append(StyleViolation(line: lineNumber, kind: .wrongIndentSize))
+                    }
+                }
+            }
+
+            if policy.trimTrailingWhitespace, let last = line.last, last == " " || last == "\t" {
+                violations.append(StyleViolation(line: lineNumber, kind: .trailingWhitespace))
+            }
+        }
+
+        if policy.requireFinalNewline, !lines.isEmpty, !TextIndentation.hasFinalNewline(text) {
+            violations.append(StyleViolation(line: lines.count, kind: .missingFinalNewline))
+        }
+
+        return violations
+    }
+}
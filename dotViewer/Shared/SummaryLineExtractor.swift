@@ -0,0 +1,76 @@
+import Foundation
+
+public struct SummaryLine: Equatable, Sendable {
+    /// 1-based display line number.
+    public let lineNumber: Int
+    /// The line's highlighted runs, with surrounding whitespace trimmed.
+    public let runs: [HighlightRun]
+
+    public init(lineNumber: Int, runs: [HighlightRun]) {
+        self.lineNumber = lineNumber
+        self.runs = runs
+    }
+
+    public var text: String { HighlightRuns.plainText(of: runs) }
+}
+
+/// Picks a one-line "title" for a file: the first markdown H1, or the first top-level
+/// declaration (function, type, class, …) in source code.
+public enum SummaryLineExtractor {
+    private static let declarationKeywords: Set<String> = [
+        "fn", "func", "function", "def", "class", "struct", "enum", "interface", "protocol",
+        "trait", "impl", "extension", "type", "typealias", "module", "object", "record",
+        "actor", "union", "namespace", "package", "defmodule", "defn"
+    ]
+
+    private static let modifierKeywords: Set<String> = [
+        "pub", "pub(crate)", "public", "private", "fileprivate", "internal", "open", "export",
+        "default", "static", "final", "abstract", "sealed", "async", "unsafe", "extern",
+        "data", "inline", "override", "@objc", "@main", "const"
+    ]
+
+    private static let commentPrefixes = ["//", "#", "/*", "*", "--", ";", "%"]
+
+    /// `tokens` come from a whole-document parse and color the returned line.
+    public static func summaryLine(in text: String, tokens: [HighlightToken], languageId: String, maxLines: Int = 500) -> SummaryLine? {
+        let lines = TextLineUtilities.displayLines(in: text).prefix(maxLines)
+
+        func summary(at lineNumber: Int) -> SummaryLine {
+            let runs = HighlightRuns.lines(in: text, tokens: tokens, range: lineNumber...lineNumber).first?.runs ?? []
+            return SummaryLine(lineNumber: lineNumber, runs: HighlightRuns.trimmingWhitespace(runs))
+        }
+
+        if languageId.lowercased() == "markdown" {
+            for (index, line) in lines.enumerated() {
+                let trimmed = line.trimmingCharacters(in: .whitespaces)
+                if trimmed.hasPrefix("# ") {
+                    return summary(at: index + 1)
+                }
+            }
+            return nil
+        }
+
+        for (index, line) in lines.enumerated() {
+            let trimmed = line.trimmingCharacters(in: .whitespaces)
+            guard !trimmed.isEmpty,
+                  !commentPrefixes.contains(where: { trimmed.hasPrefix($0) })
+            else { continue }
+
+            if isDeclaration(trimmed) {
+                return summary(at: index + 1)
+            }
+        }
+        return nil
+    }
+
+    private static func isDeclaration(_ line: String) -> Bool {
+        let words = line.split(whereSeparator: { $0 == " " || $0 == "\t" }).map(String.init)
+        for word in words {
+            if modifierKeywords.contains(word) {
+                continue
+            }
+            return declarationKeywords.contains(word)
+        }
+        return false
+    }
+}
@@ -0,0 +1,69 @@
+import Foundation
+
+public enum TextBlockKind: String, Sendable {
+    case paragraph
+    case code
+    case heading
+    case list
+    case blank
+}
+
+public struct TextBlock: Equatable, Sendable {
+    /// 1-based, inclusive display line range.
+    public let startLine: Int
+    public let endLine: Int
+    public let kind: TextBlockKind
+
+    public init(startLine: Int, endLine: Int, kind: TextBlockKind) {
+        self.startLine = startLine
+        self.endLine = endLine
+        self.kind = kind
+    }
+}
+
+/// Splits prose into blocks so previews can add paragraph spacing. Markdown gets headings, fenced
+/// code, and lists; every other language is split on blank lines only.
+public enum TextBlockDetector {
+    public static func blocks(in text: String, languageId: String) -> [TextBlock] {
+        let lines = TextLineUtilities.displayLines(in: text)
+        let isMarkdown = languageId.lowercased() == "markdown"
+        var blocks: [TextBlock] = []
+        var openFence: String?
+
+        for (index, line) in lines.enumerated() {
+            let lineNumber = index + 1
+            let trimmed = line.trimmingCharacters(in: .whitespaces)
+            let kind: TextBlockKind
+
+            if isMarkdown, let fence = openFence {
+                kind = .code
+                if trimmed.hasPrefix(fence) {
+                    openFence = nil
+                }
+            } else if isMarkdown, trimmed.hasPrefix("```") || trimmed.hasPrefix("~~~") {
+                kind = .code
+                openFence = String(trimmed.prefix(3))
+            } else if trimmed.isEmpty {
+                kind = .blank
+            } else if isMarkdown, trimmed.range(of: #"^#{1,6}(\s|$)"#, options: .regularExpression) != nil {
+                kind = .heading
+            } else if isMarkdown, MarkdownRenderer.isListItem(line) {
+                kind = .list
+            } else if isMarkdown, blocks.last?.kind == .list, blocks.last?.endLine == index, line.hasPrefix(" ") {
+                kind = .list
+            } else {
+                kind = .paragraph
+            }
+
+            // Headings are always single-line blocks; a fence opening right after a code block
+            // starts a new one.
+            let startsFence = kind == .code && openFence != nil && (trimmed.hasPrefix("```") || trimmed.hasPrefix("~~~"))
+            if let last = blocks.last, last.kind == kind, kind != .heading, !startsFence {
+                blocks[blocks.count - 1] = TextBlock(startLine: last.startLine, endLine: lineNumber, kind: kind)
+            } else {
+                blocks.append(TextBlock(startLine: lineNumber, endLine: lineNumber, kind: kind))
+            }
+        }
+        return blocks
+    }
+}
@@ -0,0 +1,186 @@
+import Foundation
+
+public enum IndentStyle: String, Sendable {
+    case spaces
+    case tabs
+}
+
+public struct DetectedIndentation: Equatable, Sendable {
+    public let style: IndentStyle
+    public let width: Int
+
+    public init(style: IndentStyle, width: Int) {
+        self.style = style
+        self.width = width
+    }
+}
+
+public struct IndentationGuideLine: Equatable, Sendable {
+    /// 1-based display line number.
+    public let line: Int
+    /// Visual columns (tabs expanded) where a vertical guide should be drawn.
+    public let columns: [Int]
+
+    public init(line: Int, columns: [Int]) {
+        self.line = line
+        self.columns = columns
+    }
+}
+
+public struct TabStopSpan: Equatable, Sendable {
+    /// 1-based display line number.
+    public let line: Int
+    /// 0-based character index of the tab within the line.
+    public let characterIndex: Int
+    /// Visual columns the expanded tab covers, ending on a tab stop.
+    public let columns: Range<Int>
+
+    public init(line: Int, characterIndex: Int, columns: Range<Int>) {
+        self.line = line
+        self.characterIndex = characterIndex
+        self.columns = columns
+    }
+}
+
+public enum TextIndentation {
+    public static func leadingWhitespace(of line: String) -> Substring {
+        line.prefix(while: { $0 == " " || $0 == "\t" })
+    }
+
+    public static func isBlank(_ line: String) -> Bool {
+        leadingWhitespace(of: line).count == line.count
+    }
+
+    /// Infers the dominant indentation of a document.
+    ///
+    /// Tab-indented lines vote for `.tabs`, space-indented lines vote for `.spaces` and the
+    /// width is the greatest common divisor of their indents. Returns nil when nothing is indented.
+    public static func detect(in text: String) -> DetectedIndentation? {
+        var tabLines = 0
+        var spaceLines = 0
+        var width = 0
+
+        for line in TextLineUtilities.displayLines(in: text) where !isBlank(line) {
+            let leading = leadingWhitespace(of: line)
+            guard let first = leading.first else { continue }
+            if first == "\t" {
+                tabLines += 1
+            } else {
+                spaceLines += 1
+                width = greatestCommonDivisor(width, leading.prefix(while: { $0 == " " }).count)
+            }
+        }
+
+        if tabLines == 0 && spaceLines == 0 {
+            return nil
+        }
+        if tabLines > spaceLines {
+            return DetectedIndentation(style: .tabs, width: 1)
+        }
+        return DetectedIndentation(style: .spaces, width: max(width, 1))
+    }
+
+    /// Visual width of a line's indentation, expanding tabs to `tabWidth` stops.
+    public static func visualIndentWidth(of line: String, tabWidth: Int) -> Int {
+        let stop = max(tabWidth, 1)
+        var column = 0
+        for character in leadingWhitespace(of: line) {
+            column += character == "\t" ? stop - column % stop : 1
+        }
+        return column
+    }
+
+    /// Columns `text` occupies when it starts at `startColumn`: tabs advance to the next
+    /// `tabWidth` stop and wide East Asian characters and emoji count as two columns.
+    public static func visualWidth(of text: some StringProtocol, tabWidth: Int, startColumn: Int = 0) -> Int {
+        let stop = max(tabWidth, 1)
+        var column = startColumn
+        for character in text {
+            column += character == "\t" ? stop - column % stop : columnWidth(of: character)
+        }
+        return column - startColumn
+    }
+
+    /// `visualWidth(of:tabWidth:)` of every display line, for gutters and wrap decisions.
+    public static func lineVisualWidths(in text: String, tabWidth: Int) -> [Int] {
+        TextLineUtilities.displayLines(in: text).map { visualWidth(of: $0, tabWidth: tabWidth) }
+    }
+
+    /// Terminal-style column width of one grapheme, ignoring tabs.
+    static func columnWidth(of character: Character) -> Int {
+        guard let scalar = character.unicodeScalars.first else { return 0 }
+        if scalar.properties.isEmojiPresentation || character.unicodeScalars.contains("\u{FE0F}") {
+            return 2
+        }
+        return wideRanges.contains { $0.contains(scalar.value) } ? 2 : 1
+    }
+
+    /// East Asian Wide and Fullwidth blocks.
+    private static let wideRanges: [ClosedRange<UInt32>] = [
+        0x1100...0x115F, 0x2E80...0x303E, 0x3041...0x33FF, 0x3400...0x4DBF, 0x4E00...0x9FFF,
+        0xA000...0xA4CF, 0xAC00...0xD7A3, 0xF900...0xFAFF, 0xFE30...0xFE4F, 0xFF00...0xFF60,
+        0xFFE0...0xFFE6, 0x20000...0x3FFFD,
+    ]
+
+    /// Every tab in `text` with the visual columns it expands to, for drawing tab-stop guides.
+    /// Tabs after other characters are included, so alignment tabs can be debugged too.
+    public static func tabStops(in text: String, tabWidth: Int) -> [TabStopSpan] {
+        let stop = max(tabWidth, 1)
+        var spans: [TabStopSpan] = []
+        for (index, line) in TextLineUtilities.displayLines(in: text).enumerated() where line.contains("\t") {
+            var column = 0
+            for (characterIndex, character) in line.enumerated() {
+                guard character == "\t" else {
+                    column += columnWidth(of: character)
+                    continue
+                }
+                let next = column + stop - column % stop
+                spans.append(TabStopSpan(line: index + 1, characterIndex: characterIndex, columns: column..<next))
+                column = next
+            }
+        }
+        return spans
+    }
+
+    /// One guide per indentation level a line sits inside, e.g. a line indented two levels with a
+    /// 4-column unit gets guides at columns 0 and 4. Blank lines inherit the shallower of their
+    /// neighbours so guides run unbroken through gaps inside a block.
+    public static func indentationGuides(in text: String, tabWidth: Int) -> [IndentationGuideLine] {
+        let lines = TextLineUtilities.displayLines(in: text)
+        let detected = detect(in: text)
+        let unit = detected?.style == .spaces ? detected?.width ?? tabWidth : tabWidth
+        guard unit > 0 else { return [] }
+
+        let widths: [Int?] = lines.map { isBlank($0) ? nil : visualIndentWidth(of: $0, tabWidth: tabWidth) }
+        var resolved = [Int](repeating: 0, count: lines.count)
+        var previous = 0
+        for index in widths.indices {
+            if let width = widths[index] {
+                resolved[index] = width
+                previous = width
+            } else {
+                let next = widths[(index + 1)...].lazy.compactMap { $0 }.first ?? 0
+                resolved[index] = min(previous, next)
+            }
+        }
+
+        return resolved.enumerated().map { index, width in
+            let levels = width / unit
+            return IndentationGuideLine(line: index + 1, columns: (0..<levels).map { $0 * unit })
+        }
+    }
+
+    public static func hasFinalNewline(_ text: String) -> Bool {
+        guard let last = text.unicodeScalars.last else { return false }
+        return last == "\n" || last == "\r"
+    }
+
+    private static func greatestCommonDivisor(_ a: Int, _ b: Int) -> Int {
+        var a = a
+        var b = b
+        while b != 0 {
+            (a, b) = (b, a % b)
+        }
+        return a
+    }
+}
@@ -26,4 +26,47 @@ public enum TextLineUtilities {
     public static func visualLineCount(in text: String) -> Int {
         lineCount(in: text)
     }
+
+    /// The Unicode Control Picture (`␀`, `␌`, `␡`, …) standing in for a C0 control or DEL, or nil
+    /// for tab, newline, carriage return and everything else.
+    public static func controlPicture(for scalar: Unicode.Scalar) -> Unicode.Scalar? {
+        switch scalar.value {
+        case 0x09, 0x0A, 0x0D:
+            return nil
+        case 0x00..<0x20:
+            return Unicode.Scalar(0x2400 + scalar.value)
+        case 0x7F:
+            return Unicode.Scalar(0x2421)
+        default:
+            return nil
+        }
+    }
+
+    /// `text` with control characters swapped for their pictures, one scalar for one scalar, so
+    /// exporters never emit raw NUL or form-feed bytes and character columns stay aligned. Token
+    /// offsets are applied to the original text before this runs.
+    public static func visibleControlCharacters(_ text: String) -> String {
+        guard text.unicodeScalars.contains(where: { controlPicture(for: $0) != nil }) else { return text }
+        var result = String.UnicodeScalarView()
+        for scalar in text.unicodeScalars {
+            result.append(controlPicture(for: scalar) ?? scalar)
+        }
+        return String(result)
+    }
+
+    /// Replaces each run of whitespace-only lines with a single empty line, for dense overviews.
+    public static func collapsingBlankRuns(_ lines: [String]) -> [String] {
+        var result: [String] = []
+        var previousWasBlank = false
+        for line in lines {
+            let isBlank = line.allSatisfy { $0 == " " || $0 == "\t" }
+            if isBlank {
+                if !previousWasBlank { result.append("") }
+            } else {
+                result.append(line)
+            }
+            previousWasBlank = isBlank
+        }
+        return result
+    }
 }
@@ -0,0 +1,282 @@
+import Foundation
+
+public enum TextMateThemeError: Error, Equatable {
+    case invalidPropertyList
+    case invalidJSON
+    case missingGlobalSettings
+    case invalidColor(String)
+}
+
+public struct TextMateTheme: Equatable, Sendable {
+    public let palette: ThemePalette
+    /// The theme selector that won for each token color, for theme authors debugging why a token
+    /// got its color. Tokens that fell back to the foreground have no entry.
+    public let matchedSelectors: [TokenType: String]
+    /// Current-line tint from the global settings, `#RRGGBB` or `#RRGGBBAA` when translucent.
+    public let lineHighlight: String?
+    /// Selection tint from the global settings, `#RRGGBB` or `#RRGGBBAA` when translucent.
+    public let selection: String?
+    /// Every scope selector the theme has a rule for, in file order and without duplicates, so
+    /// authors can check whether a scope is styled at all.
+    public let scopes: [String]
+
+    public init(
+        palette: ThemePalette,
+        matchedSelectors: [TokenType: String],
+        lineHighlight: String? = nil,
+        selection: String? = nil,
+        scopes: [String] = []
+    ) {
+        self.palette = palette
+        self.matchedSelectors = matchedSelectors
+        self.lineHighlight = lineHighlight
+        self.selection = selection
+        self.scopes = scopes
+    }
+}
+
+/// Theme color with alpha kept until serialization, so colors that differ only in alpha stay
+/// distinct and translucent overlays don't turn opaque.
+struct RGBAColor: Hashable, Sendable {
+    let rgb: RGBColor
+    let alpha: UInt8
+
+    /// Accepts `#RGB`, `#RRGGBB` and `#RRGGBBAA`.
+    init?(hex value: String) {
+        var digits = value.trimmingCharacters(in: .whitespaces)
+        if digits.hasPrefix("#") { digits.removeFirst() }
+        if digits.count == 3 {
+            digits = digits.map { "\($0)\($0)" }.joined()
+        }
+        var alpha: UInt8 = 0xFF
+        if digits.count == 8 {
+            guard digits.suffix(2).allSatisfy(\.isHexDigit), let parsed = UInt8(digits.suffix(2), radix: 16) else {
+                return nil
+            }
+            alpha = parsed
+            digits = String(digits.prefix(6))
+        }
+        guard let rgb = RGBColor(hex: digits) else { return nil }
+        self.rgb = rgb
+        self.alpha = alpha
+    }
+
+    /// `#RRGGBB` when opaque, `#RRGGBBAA` otherwise.
+    var hex: String {
+        alpha == 0xFF ? rgb.hex : rgb.hex + String(format: "%02X", Int(alpha))
+    }
+}
+
+/// Converts a `.tmTheme` property list into a one-off `ThemePalette`. Nothing is registered: the
+/// palette lives only as long as the caller keeps it, so ad-hoc themes never appear in
+/// `ThemePalette.selectableThemes`.
+public enum TextMateThemeParser {
+    /// Scope candidates per token, most specific first. Each candidate also matches descendant
+    /// scopes, and falls back to ancestor selectors (e.g. `constant` for `constant.numeric`).
+    private static let scopeCandidates: [(TokenType, [String])] = [
+        (.comment, ["comment"]),
+        (.keyword, ["keyword.control", "keyword", "storage.modifier", "storage"]),
+        (.string, ["string"]),
+        (.number, ["constant.numeric"]),
+        (.type, ["entity.name.type", "support.type", "storage.type", "entity.name.class"]),
+        (.function, ["entity.name.function", "support.function", "meta.function-call"]),
+        (.property, ["variable.other.property", "variable.other.member", "support.type.property-name", "variable"]),
+        (.punctuation, ["punctuation"]),
+        (.tag, ["entity.name.tag"]),
+        (.attribute, ["entity.other.attribute-name"]),
+        (.escape, ["constant.character.escape"]),
+        (.builtin, ["support.function.builtin", "variable.language", "support"]),
+        (.namespace, ["entity.name.namespace", "entity.name.module"]),
+        (.parameter, ["variable.parameter"]),
+    ]
+
+    private struct Rule {
+        let selectors: [String]
+        let foreground: String
+    }
+
+    public static func palette(from data: Data) throws -> ThemePalette {
+        try parse(data).palette
+    }
+
+    public static func parse(_ data: Data) throws -> TextMateTheme {
+        guard let root = try? PropertyListSerialization.propertyList(from: data, format: nil) as? [String: Any],
+              let entries = root["settings"] as? [[String: Any]]
+        else {
+            throw TextMateThemeError.invalidPropertyList
+        }
+        return try theme(named: root["name"] as? String, entries: entries)
+    }
+
+    /// Parses a VS Code color theme (`.json`, comments and trailing commas allowed). The editor
+    /// colors become the global settings and `tokenColors` the scope rules; other workbench
+    /// colors and `include`d files are ignored.
+    public static func parseVSCodeTheme(_ data: Data) throws -> TextMateTheme {
+        guard let root = try? JSONSerialization.jsonObject(with: data, options: .json5Allowed) as? [String: Any] else {
+            throw TextMateThemeError.invalidJSON
+        }
+        let colors = root["colors"] as? [String: Any] ?? [:]
+        // VS Code paints themes without an editor background in its default dark or light gray.
+        let defaultBackgrounds = ["dark": "#1E1E1E", "hc-black": "#000000", "light": "#FFFFFF", "hc-light": "#FFFFFF"]
+
+        var global: [String: Any] = [:]
+        let keys = [
+            ("background", "editor.background"),
+            ("foreground", "editor.foreground"),
+            ("caret", "editorCursor.foreground"),
+            ("lineHighlight", "editor.lineHighlightBackground"),
+            ("selection", "editor.selectionBackground"),
+        ]
+        for (key, colorKey) in keys {
+            global[key] = colors[colorKey] as? String
+        }
+        if global["background"] == nil {
+            global["background"] = (root["type"] as? String).flatMap { defaultBackgrounds[$0.lowercased()] }
+        }
+
+        var entries: [[String: Any]] = [["settings": global]]
+        for rule in root["tokenColors"] as? [[String: Any]] ?? [] {
+            let scope = (rule["scope"] as? String) ?? (rule["scope"] as? [String])?.joined(separator: ", ")
+            guard let scope, let settings = rule["settings"] as? [String: Any] else { continue }
+            entries.append(["scope": scope, "settings": settings])
+        }
+        return try theme(named: root["name"] as? String, entries: entries)
+    }
+
+    private static func theme(named name: String?, entries: [[String: Any]]) throws -> TextMateTheme {
+        guard let global = entries.first(where: { $0["scope"] == nil })?["settings"] as? [String: Any],
+              let backgroundValue = global["background"] as? String
+        else {
+            throw TextMateThemeError.missingGlobalSettings
+        }
+
+        let background = try normalizedHex(backgroundValue)
+        let isDark = RGBColor(hex: background)?.isDark ?? false
+        // Themes without a foreground get one that reads on their background, not a fixed white.
+        let foreground = try (global["foreground"] as? String).map(normalizedHex) ?? (isDark ? "#FFFFFF" : "#000000")
+
+        var rules: [Rule] = []
+        var scopes: [String] = []
+        for entry in entries {
+            if let scope = entry["scope"] as? String {
+                for selector in scope.split(separator: ",") {
+                    let trimmed = selector.trimmingCharacters(in: .whitespacesAndNewlines)
+                    if !trimmed.isEmpty, !scopes.contains(trimmed) {
+                        scopes.append(trimmed)
+                    }
+                }
+            }
+            guard let scope = entry["scope"] as? String,
+                  let settings = entry["settings"] as? [String: Any],
+                  let value = settings["foreground"] as? String,
+                  let color = try? normalizedHex(value)
+            else { continue }
+            let selectors = scope.split(separator: ",").compactMap { selector in
+                selector.split(separator: " ").last.map { $0.trimmingCharacters(in: .whitespaces) }
+            }
+            rules.append(Rule(selectors: selectors, foreground: color))
+        }
+
+        var colors: [TokenType: String] = [:]
+        var selectors: [TokenType: String] = [:]
+        for (token, candidates) in scopeCandidates {
+            let match = winningRule(for: candidates, in: rules)
+            colors[token] = match?.color ?? foreground
+            selectors[token] = match?.selector
+        }
+        let caret = (global["caret"] as? String).flatMap { try? normalizedHex($0) }
+        let lineHighlight = (global["lineHighlight"] as? String).flatMap { RGBAColor(hex: $0)?.hex }
+        let selection = (global["selection"] as? String).flatMap { RGBAColor(hex: $0)?.hex }
+
+        let palette = ThemePalette(
+            name: name ?? "Custom Theme",
+            isDark: isDark,
+            background: background,
+            text: foreground,
+            comment: colors[.comment] ?? foreground,
+            keyword: colors[.keyword] ?? foreground,
+            string: colors[.string] ?? foreground,
+            number: colors[.number] ?? foreground,
+            type: colors[.type] ?? foreground,
+            function: colors[.function] ?? foreground,
+            property: colors[.property] ?? foreground,
+            punctuation: colors[.punctuation] ?? foreground,
+            accent: caret ?? colors[.keyword] ?? foreground,
+            tag: colors[.tag] ?? foreground,
+            attribute: colors[.attribute] ?? foreground,
+            escape: colors[.escape] ?? foreground,
+            builtin: colors[.builtin] ?? foreground,
+            namespace: colors[.namespace] ?? foreground,
+            parameter: colors[.parameter] ?? foreground
+        )
+        return TextMateTheme(
+            palette: palette,
+            matchedSelectors: selectors,
+            lineHighlight: lineHighlight,
+            selection: selection,
+            scopes: scopes
+        )
+    }
+
+    /// Parses every `.tmTheme` and VS Code `.json` theme directly inside `directory`, sorted by
+    /// file name. Files that fail to parse are skipped so one bad theme doesn't hide the rest; an
+    /// unreadable directory throws.
+    public static func themes(inDirectory directory: URL) throws -> [TextMateTheme] {
+        let files = try FileManager.default.contentsOfDirectory(at: directory, includingPropertiesForKeys: nil)
+        return files
+            .filter { ["tmtheme", "json"].contains($0.pathExtension.lowercased()) }
+            .sorted { $0.lastPathComponent < $1.lastPathComponent }
+            .compactMap { file in
+                guard let data = try? Data(contentsOf: file) else { return nil }
+                return file.pathExtension.lowercased() == "json" ? try? parseVSCodeTheme(data) : try? parse(data)
+            }
+    }
+
+    /// Token that a TextMate scope (`comment.line`, `keyword.control`) styles, or nil when none of
+    /// the table's candidates relate to it. Plain token names such as `comment` also work.
+    static func tokenType(forScope scope: String) -> TokenType? {
+        let scope = scope.trimmingCharacters(in: .whitespaces).lowercased()
+        if let token = TokenType(rawValue: scope) {
+            return token
+        }
+        let matches: (String) -> Bool = { candidate in
+            scope == candidate || scope.hasPrefix(candidate + ".") || candidate.hasPrefix(scope + ".")
+        }
+        return scopeCandidates.first { $0.1.contains(where: matches) }?.0
+    }
+
+    /// TextMate precedence: an exact selector beats an ancestor (`constant` for `constant.numeric`),
+    /// a longer ancestor beats a shorter one, and among equals the later rule wins. Descendant
+    /// selectors (`string.quoted` for `string`) only apply when nothing else does, shortest first,
+    /// so themes that style only specific scopes still color the token.
+    private static func winningRule(for candidates: [String], in rules: [Rule]) -> (selector: String, color: String)? {
+        let pairs = rules.flatMap { rule in rule.selectors.map { (selector: $0, color: rule.foreground) } }
+
+        for candidate in candidates {
+            if let exact = pairs.last(where: { $0.selector == candidate }) {
+                return exact
+            }
+        }
+        for candidate in candidates {
+            let ancestors = pairs.filter { candidate.hasPrefix($0.selector + ".") }
+            if let longest = ancestors.map(\.selector.count).max() {
+                return ancestors.last { $0.selector.count == longest }
+            }
+        }
+        for candidate in candidates {
+            let descendants = pairs.filter { $0.selector.hasPrefix(candidate + ".") }
+            if let shortest = descendants.map(\.selector.count).min() {
+                return descendants.last { $0.selector.count == shortest }
+            }
+        }
+        return nil
+    }
+
+    /// Opaque uppercase `#RRGGBB` for palette slots, which are painted as solid token colors.
+    static func normalizedHex(_ value: String) throws -> String {
+        guard let color = RGBAColor(hex: value) else {
+            throw TextMateThemeError.invalidColor(value)
+        }
+        return color.rgb.hex
+    }
+}
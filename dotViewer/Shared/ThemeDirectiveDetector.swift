@@ -0,0 +1,28 @@
+import Foundation
+
+/// Reads a per-file theme override such as `// dotviewer-theme: tokyoNight` from a comment in
+/// the first few lines of a file.
+public enum ThemeDirectiveDetector {
+    private static let marker = "dotviewer-theme:"
+    private static let commentPrefixes = ["//", "#", "--", "/*", "<!--", ";", "%", "*"]
+    private static let commentSuffixes = ["*/", "-->"]
+
+    /// Returns the selectable theme id named by the directive, or nil when there is no directive
+    /// or it names an unknown theme. Theme ids match case-insensitively.
+    public static func theme(in text: String, maxLines: Int = 5) -> String? {
+        for line in TextLineUtilities.displayLines(in: text).prefix(maxLines) {
+            let trimmed = line.trimmingCharacters(in: .whitespaces)
+            guard commentPrefixes.contains(where: { trimmed.hasPrefix($0) }),
+                  let range = trimmed.range(of: marker, options: .caseInsensitive)
+            else { continue }
+
+            var value = trimmed[range.upperBound...].trimmingCharacters(in: .whitespaces)
+            for suffix in commentSuffixes where value.hasSuffix(suffix) {
+                value = String(value.dropLast(suffix.count)).trimmingCharacters(in: .whitespaces)
+            }
+
+            return ThemePalette.selectableThemes.first { $0.id.lowercased() == value.lowercased() }?.id
+        }
+        return nil
+    }
+}
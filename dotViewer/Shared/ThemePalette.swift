@@ -3,7 +3,7 @@ import Foundation
 /// All syntax token types used in highlighting.
 /// Adding a case here forces updates to `ThemePalette.hex(for:)`,
 /// `PreviewHTMLBuilder` CSS generation, and `TokenColorMapper`.
-public enum TokenType: String, CaseIterable, Sendable {
+public enum TokenType: String, CaseIterable, Codable, Sendable {
     case comment
     case keyword
     case string
@@ -23,6 +23,56 @@ public enum TokenType: String, CaseIterable, Sendable {
     case identifier  // uses text color
 }
 
+/// Emphasis as separate flags, so consumers don't decode the bitmask themselves.
+public struct FontStyleFlags: Equatable, Sendable {
+    public let isBold: Bool
+    public let isItalic: Bool
+    public let isUnderline: Bool
+
+    public init(isBold: Bool = false, isItalic: Bool = false, isUnderline: Bool = false) {
+        self.isBold = isBold
+        self.isItalic = isItalic
+        self.isUnderline = isUnderline
+    }
+
+    /// Decodes `1` bold, `2` italic, `4` underline; combinations add (bold+underline is `5`).
+    public init(bitmask: Int) {
+        self.init(isBold: bitmask & 1 != 0, isItalic: bitmask & 2 != 0, isUnderline: bitmask & 4 != 0)
+    }
+
+    public var bitmask: Int {
+        (isBold ? 1 : 0) | (isItalic ? 2 : 0) | (isUnderline ? 4 : 0)
+    }
+
+    public static let plain = FontStyleFlags()
+    public static let bold = FontStyleFlags(isBold: true)
+    public static let italic = FontStyleFlags(isItalic: true)
+}
+
+extension TokenType {
+    /// Emphasis for renderers without CSS (thumbnails, rich text).
+    public var styleFlags: FontStyleFlags {
+        switch self {
+        case .comment:     return .italic
+        case .keyword:     return .bold
+        case .string:      return .plain
+        case .number:      return .plain
+        case .type:        return .bold
+        case .function:    return .plain
+        case .property:    return .plain
+        case .constant:    return .plain
+        case .identifier:  return .plain
+        case .punctuation: return .plain
+        case .tag:         return .bold
+        case .attribute:   return .italic
+        case .escape:      return .plain
+        case .builtin:     return .italic
+        case .namespace:   return .plain
+        case .parameter:   return .plain
+        }
+    }
+}
+
 public struct ThemeChoice: Identifiable, Equatable, Sendable {
     public let id: String
     public let title: String
@@ -33,6 +83,82 @@ public struct ThemeChoice: Identifiable, Equatable, Sendable {
     }
 }
 
+/// A user tweak to one part of a theme, e.g. brighter comments. `scope` is a token name
+/// (`comment`) or TextMate scope (`comment.line`); nil fields keep the theme's value.
+/// Colors apply through `ThemePalette.applying(_:)`; font styles through the HTML preview's
+/// token CSS (`PreviewInfo.styleOverrides`) and RTF export.
+public struct ScopeOverride: Equatable, Sendable {
+    public let scope: String
+    public let foreground: String?
+    public let fontStyle: FontStyleFlags?
+
+    public init(scope: String, foreground: String? = nil, fontStyle: FontStyleFlags? = nil) {
+        self.scope = scope
+        self.foreground = foreground
+        self.fontStyle = fontStyle
+    }
+
+    /// Font style for `token` after `overrides`; the last matching override wins.
+    public static func styleFlags(for token: TokenType, overrides: [ScopeOverride]) -> FontStyleFlags {
+        overrides.last { $0.fontStyle != nil && TextMateThemeParser.tokenType(forScope: $0.scope) == token }?.fontStyle
+            ?? token.styleFlags
+    }
+}
+
+/// A theme entry for settings lists; custom themes can be removed, built-in ones only reset.
+public struct ThemeInfo: Identifiable, Equatable, Sendable {
+    public let id: String
+    public let title: String
+    public let isBuiltIn: Bool
+
+    public init(id: String, title: String, isBuiltIn: Bool) {
+        self.id = id
+        self.title = title
+        self.isBuiltIn = isBuiltIn
+    }
+}
+
+/// Light and dark theme ids from one visual family, for switching instantly on appearance change.
+public struct ThemePair: Equatable, Sendable {
+    public let light: String
+    public let dark: String
+
+    public init(light: String, dark: String) {
+        self.light = light
+        self.dark = dark
+    }
+}
+
+/// The built-in palette an app theme setting actually renders with, so hosts can show or log it.
+public struct ResolvedTheme: Equatable, Sendable {
+    /// Id of the concrete palette, never a system-following id like `auto`.
+    public let id: String
+    public let name: String
+    public let background: String
+    public let isDark: Bool
+
+    public init(id: String, name: String, background: String, isDark: Bool) {
+        self.id = id
+        self.name = name
+        self.background = background
+        self.isDark = isDark
+    }
+}
+
+public struct ThemeBackground: Identifiable, Equatable, Sendable {
+    public let id: String
+    public let background: String
+
+    public init(id: String, background: String) {
+        self.id = id
+        self.background = background
+    }
+}
+
+public enum ThemePaletteError: Error, Equatable {
+    case invalidHexColor(String)
+}
+
 public struct ThemePalette: Equatable, Sendable {
     public let name: String
     public let isDark: Bool
@@ -77,6 +203,120 @@ public struct ThemePalette: Equatable, Sendable {
         }
     }
 
+    /// Dark/light classification computed from background luminance rather than the declared flag.
+    public var backgroundIsDark: Bool {
+        RGBColor(hex: background)?.isDark ?? isDark
+    }
+
+    /// Dimmed foreground for line-number gutters.
+    public var gutter: String {
+        isDark ? "#3B3F51" : "#C0C4CC"
+    }
+
+    /// Caret color for editable surfaces: the accent when it stands out against the background
+    /// (WCAG 3:1 for non-text UI), then the text color, then black or white.
+    public var caretColor: String {
+        guard let backgroundColor = RGBColor(hex: background) else { return text }
+        for candidate in [accent, text] {
+            if let color = RGBColor(hex: candidate), color.contrastRatio(with: backgroundColor) >= 3 {
+                return color.hex
+            }
+        }
+        return backgroundColor.isDark ? "#FFFFFF" : "#000000"
+    }
+
+    /// Low-distraction copy that keeps only five of the theme's colors: keyword, string (escapes
+    /// included), comment, number, and the text color for everything else.
+    public func simplified() -> ThemePalette {
+        copy { token in
+            switch token {
+            case .comment, .keyword, .string, .number: return hex(for: token)
+            case .tag, .builtin: return keyword
+            case .escape: return string
+            default: return text
+            }
+        }
+    }
+
+    /// Translucent accent for selected text: tinted to the theme yet light enough that token
+    /// colors stay readable through it. A CSS `rgba(...)` value.
+    public var selectionBackground: String {
+        guard let color = RGBColor(hex: accent) ?? RGBColor(hex: text) else { return "rgba(0, 122, 255, 0.2)" }
+        let alpha = backgroundIsDark ? "0.35" : "0.25"
+        return "rgba(\(color.red), \(color.green), \(color.blue), \(alpha))"
+    }
+
+    /// Copy where text and token colors below `minimumRatio` (WCAG contrast, e.g. 4.5) against the
+    /// background are nudged lighter or darker until they meet it; compliant colors are untouched.
+    public func withMinimumContrast(_ minimumRatio: Double) -> ThemePalette {
+        guard let backgroundColor = RGBColor(hex: background) else { return self }
+        func adjust(_ hex: String) -> String {
+            guard let color = RGBColor(hex: hex) else { return hex }
+            let adjusted = color.adjustedForContrast(against: backgroundColor, minimumRatio: minimumRatio)
+            return adjusted == color ? hex : adjusted.hex
+        }
+        return copy(text: adjust(text), accent: adjust(accent)) { adjust(hex(for: $0)) }
+    }
+
+    /// Copy with each override's foreground applied to the token its scope maps to. Scopes that
+    /// map to no token are ignored; a malformed color throws.
+    public func applying(_ overrides: [ScopeOverride]) throws -> ThemePalette {
+        var colors: [TokenType: String] = [:]
+        for override in overrides {
+            guard let foreground = override.foreground else { continue }
+            guard let color = RGBColor(hex: foreground) else {
+                throw ThemePaletteError.invalidHexColor(foreground)
+            }
+            if let token = TextMateThemeParser.tokenType(forScope: override.scope) {
+                // Constants share the number slot.
+                colors[token == .constant ? .number : token] = color.hex
+            }
+        }
+        guard !colors.isEmpty else { return self }
+        return copy { colors[$0] ?? hex(for: $0) }
+    }
+
+    /// Copy with a user-chosen background in place of the theme's; `isDark` follows the new
+    /// background so chrome stays readable. `hex` must be `#RRGGBB`.
+    public func withBackground(_ hex: String) throws -> ThemePalette {
+        guard hex.count == 7, hex.hasPrefix("#"), let color = RGBColor(hex: hex) else {
+            throw ThemePaletteError.invalidHexColor(hex)
+        }
+        return copy(isDark: color.isDark, background: color.hex) { self.hex(for: $0) }
+    }
+
+    /// The single place copies are built, so a new color slot only needs adding here. Token slots
+    /// come from `tokenColor`; the rest keep this palette's values unless given.
+    private func copy(
+        isDark: Bool? = nil,
+        background: String? = nil,
+        text: String? = nil,
+        accent: String? = nil,
+        tokenColor: (TokenType) -> String
+    ) -> ThemePalette {
+        ThemePalette(
+            name: name,
+            isDark: isDark ?? self.isDark,
+            background: background ?? self.background,
+            text: text ?? self.text,
+            comment: tokenColor(.comment),
+            keyword: tokenColor(.keyword),
+            string: tokenColor(.string),
+            number: tokenColor(.number),
+            type: tokenColor(.type),
+            function: tokenColor(.function),
+            property: tokenColor(.property),
+            punctuation: tokenColor(.punctuation),
+            accent: accent ?? self.accent,
+            tag: tokenColor(.tag),
+            attribute: tokenColor(.attribute),
+            escape: tokenColor(.escape),
+            builtin: tokenColor(.builtin),
+            namespace: tokenColor(.namespace),
+            parameter: tokenColor(.parameter)
+        )
+    }
+
     public static let selectableThemes: [ThemeChoice] = [
         ThemeChoice(id: "auto", title: "Atom One (System)"),
         ThemeChoice(id: "atomOneLight", title: "Atom One Light"),
@@ -94,6 +334,45 @@ public struct ThemePalette: Equatable, Sendable {
         ThemeChoice(id: "blackout", title: "Blackout"),
     ]
 
+    /// Whether `name` is a theme that ships with the app, by id or title.
+    public static func isBuiltInTheme(_ name: String) -> Bool {
+        canonicalThemeId(name) != nil
+    }
+
+    /// Built-in themes followed by `customThemes` (keyed by name). A custom theme whose name
+    /// collides with a built-in one is left out rather than shadowing it.
+    public static func availableThemes(customThemes: [TextMateTheme] = []) -> [ThemeInfo] {
+        let builtIn = selectableThemes.map { ThemeInfo(id: $0.id, title: $0.title, isBuiltIn: true) }
+        let custom = customThemes
+            .map(\.palette.name)
+            .filter { !isBuiltInTheme($0) }
+            .map { ThemeInfo(id: $0, title: $0, isBuiltIn: false) }
+        return builtIn + custom
+    }
+
+    /// `availableThemes(customThemes:)` ordered by title, case-insensitively and independent of
+    /// locale, with ids breaking ties.
+    public static func sortedThemes(customThemes: [TextMateTheme] = []) -> [ThemeInfo] {
+        availableThemes(customThemes: customThemes).sorted {
+            ($0.title.lowercased(), $0.id) < ($1.title.lowercased(), $1.id)
+        }
+    }
+
+    /// Fixed (non-system) selectable themes whose background luminance matches `isDark`.
+    public static func fixedThemes(isDark: Bool) -> [ThemeChoice] {
+        selectableThemes.filter { choice in
+            !followsSystemAppearance(theme: choice.id)
+                && palette(for: choice.id, systemIsDark: isDark).backgroundIsDark == isDark
+        }
+    }
+
+    /// Background color of every selectable theme, resolving system-following themes for `systemIsDark`.
+    public static func themeBackgrounds(systemIsDark: Bool) -> [ThemeBackground] {
+        selectableThemes.map { choice in
+            ThemeBackground(id: choice.id, background: palette(for: choice.id, systemIsDark: systemIsDark).background)
+        }
+    }
+
     public static func followsSystemAppearance(theme: String) -> Bool {
         systemThemePair(for: theme) != nil
     }
@@ -114,7 +393,7 @@ public struct ThemePalette: Equatable, Sendable {
     }
 
     public static func systemThemePair(for theme: String) -> (light: ThemePalette, dark: ThemePalette)? {
-        switch theme {
+        switch canonicalThemeId(theme) ?? theme {
         case "auto":
             return (.atomOneLight, .atomOneDark)
         case "githubAuto":
@@ -128,7 +407,68 @@ public struct ThemePalette: Equatable, Sendable {
         }
     }
 
-    public static func palette(for theme: String, systemIsDark: Bool) -> ThemePalette {
+    /// Light and dark ids of `theme`'s family. Accepts a variant (`solarizedLight`) or its system
+    /// theme (`solarizedAuto`); dark-only themes keep themselves for dark and use Atom One Light,
+    /// and unknown ids get the Atom One pair.
+    public static func themePair(for theme: String) -> ThemePair {
+        let theme = canonicalThemeId(theme) ?? theme
+        let families: [(system: String, pair: ThemePair)] = [
+            ("auto", ThemePair(light: "atomOneLight", dark: "atomOneDark")),
+            ("githubAuto", ThemePair(light: "githubLight", dark: "githubDark")),
+            ("xcodeAuto", ThemePair(light: "xcodeLight", dark: "xcodeDark")),
+            ("solarizedAuto", ThemePair(light: "solarizedLight", dark: "solarizedDark")),
+        ]
+        if let family = families.first(where: { [$0.system, $0.pair.light, $0.pair.dark].contains(theme) }) {
+            return family.pair
+        }
+        if let palette = knownPalette(for: theme, systemIsDark: true), palette.isDark {
+            return ThemePair(light: "atomOneLight", dark: theme)
+        }
+        return families[0].pair
+    }
+
+    /// Resolves a theme id to its palette. Unknown ids resolve `fallbackTheme` instead, and when
+    /// that is missing or unknown too, the appearance-matched Atom One palette.
+    public static func palette(for theme: String, systemIsDark: Bool, fallbackTheme: String? = nil) -> ThemePalette {
+        if let palette = knownPalette(for: theme, systemIsDark: systemIsDark) {
+            return palette
+        }
+        if let fallbackTheme, let palette = knownPalette(for: fallbackTheme, systemIsDark: systemIsDark) {
+            return palette
+        }
+        return systemIsDark ? .atomOneDark : .atomOneLight
+    }
+
+    /// Resolves `appTheme` the same way as `palette(for:systemIsDark:)`, reporting which palette
+    /// was picked: system-following themes resolve to their light or dark variant.
+    public static func resolve(appTheme: String, systemIsDark: Bool) -> ResolvedTheme {
+        let theme = canonicalThemeId(appTheme) ?? appTheme
+        let id: String
+        if followsSystemAppearance(theme: theme) {
+            let pair = themePair(for: theme)
+            id = systemIsDark ? pair.dark : pair.light
+        } else if knownPalette(for: theme, systemIsDark: systemIsDark) != nil {
+            id = theme
+        } else {
+            id = systemIsDark ? "atomOneDark" : "atomOneLight"
+        }
+        let palette = palette(for: id, systemIsDark: systemIsDark)
+        return ResolvedTheme(id: id, name: palette.name, background: palette.background, isDark: palette.backgroundIsDark)
+    }
+
+    /// Selectable theme id for `name`, accepting ids and titles regardless of case or surrounding
+    /// whitespace (`"TokyoNight"`, `" Tokyo Night "`). An exact id match wins.
+    public static func canonicalThemeId(_ name: String) -> String? {
+        if selectableThemes.contains(where: { $0.id == name }) {
+            return name
+        }
+        let normalized = name.trimmingCharacters(in: .whitespacesAndNewlines).lowercased()
+        guard !normalized.isEmpty else { return nil }
+        return selectableThemes.first(where: { $0.id.lowercased() == normalized || $0.title.lowercased() == normalized })?.id
+    }
+
+    private static func knownPalette(for theme: String, systemIsDark: Bool) -> ThemePalette? {
+        let theme = canonicalThemeId(theme) ?? theme
         if let pair = systemThemePair(for: theme) {
             return systemIsDark ? pair.dark : pair.light
         }
@@ -155,7 +495,7 @@ public struct ThemePalette: Equatable, Sendable {
         case "blackout":
             return .blackout
         default:
-            return systemIsDark ? .atomOneDark : .atomOneLight
+            return nil
         }
     }
 
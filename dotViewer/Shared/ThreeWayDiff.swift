@@ -0,0 +1,229 @@
+import Foundation
+
+public enum ThreeWayChunkStatus: String, Sendable {
+    case unchanged
+    case localChanged
+    case remoteChanged
+    /// Local and remote made the identical change.
+    case bothChanged
+    case conflict
+}
+
+public enum ThreeWaySide: Sendable {
+    case base
+    case local
+    case remote
+}
+
+public struct ThreeWayChunk: Equatable, Sendable {
+    public let status: ThreeWayChunkStatus
+    /// 0-based line ranges in each version; empty where a side has no lines for the chunk.
+    public let base: Range<Int>
+    public let local: Range<Int>
+    public let remote: Range<Int>
+
+    public init(status: ThreeWayChunkStatus, base: Range<Int>, local: Range<Int>, remote: Range<Int>) {
+        self.status = status
+        self.base = base
+        self.local = local
+        self.remote = remote
+    }
+
+    public func range(of side: ThreeWaySide) -> Range<Int> {
+        switch side {
+        case .base:
+            return base
+        case .local:
+            return local
+        case .remote:
+            return remote
+        }
+    }
+}
+
+/// All three versions highlighted, plus the chunks that align them.
+public struct ThreeWayResult: Equatable, Sendable {
+    public let base: [HighlightedLine]
+    public let local: [HighlightedLine]
+    public let remote: [HighlightedLine]
+    public let chunks: [ThreeWayChunk]
+
+    public init(base: [HighlightedLine], local: [HighlightedLine], remote: [HighlightedLine], chunks: [ThreeWayChunk]) {
+        self.base = base
+        self.local = local
+        self.remote = remote
+        self.chunks = chunks
+    }
+
+    /// The status of every line of `side`, indexed 0-based like the chunk ranges.
+    public func lineStatuses(of side: ThreeWaySide) -> [ThreeWayChunkStatus] {
+        chunks.flatMap { Array(repeating: $0.status, count: $0.range(of: side).count) }
+    }
+}
+
+/// Aligns base/local/remote versions for a merge view. Lines of `base` kept by both sides anchor
+/// the alignment; each stretch between anchors is classified by which side changed it.
+public enum ThreeWayDiff {
+    /// Edit distance past which `matches` stops searching for a shortest edit script and leaves the
+    /// differing middle unmatched, so very different inputs become one changed chunk. The search
+    /// keeps one diagonal array per edit, which bounds its memory at about `maxEdits²` integers.
+    static let maxEdits = 1_000
+
+    /// `chunks(base:local:remote:)` with each version's lines highlighted from the tokens the
+    /// highlighter produced for that version.
+    public static func highlighted(
+        base: String,
+        baseTokens: [HighlightToken],
+        local: String,
+        localTokens: [HighlightToken],
+        remote: String,
+        remoteTokens: [HighlightToken]
+    ) -> ThreeWayResult {
+        ThreeWayResult(
+            base: HighlightRuns.lines(in: base, tokens: baseTokens, range: 1...Int.max),
+            local: HighlightRuns.lines(in: local, tokens: localTokens, range: 1...Int.max),
+            remote: HighlightRuns.lines(in: remote, tokens: remoteTokens, range: 1...Int.max),
+            chunks: chunks(base: base, local: local, remote: remote)
+        )
+    }
+
+    public static func chunks(base: String, local: String, remote: String) -> [ThreeWayChunk] {
+        let baseLines = TextLineUtilities.displayLines(in: base)
+        let localLines = TextLineUtilities.displayLines(in: local)
+        let remoteLines = TextLineUtilities.displayLines(in: remote)
+        let toLocal = matches(baseLines, localLines)
+        let toRemote = matches(baseLines, remoteLines)
+
+        var chunks: [ThreeWayChunk] = []
+        var b = 0, l = 0, r = 0
+
+        func appendChunk(base: Range<Int>, local: Range<Int>, remote: Range<Int>) {
+            guard !base.isEmpty || !local.isEmpty || !remote.isEmpty else { return }
+            let original = baseLines[base]
+            let localChanged = localLines[local] != original
+            let remoteChanged = remoteLines[remote] != original
+            let status: ThreeWayChunkStatus
+            switch (localChanged, remoteChanged) {
+            case (false, false): status = .unchanged
+            case (true, false): status = .localChanged
+            case (false, true): status = .remoteChanged
+            case (true, true): status = localLines[local] == remoteLines[remote] ? .bothChanged : .conflict
+            }
+
+            if status == .unchanged, let last = chunks.last, last.status == .unchanged,
+               last.base.upperBound == base.lowerBound {
+                chunks[chunks.count - 1] = ThreeWayChunk(
+                    status: .unchanged,
+                    base: last.base.lowerBound..<base.upperBound,
+                    local: last.local.lowerBound..<local.upperBound,
+                    remote: last.remote.lowerBound..<remote.upperBound
+                )
+            } else {
+                chunks.append(ThreeWayChunk(status: status, base: base, local: local, remote: remote))
+            }
+        }
+
+        for anchor in baseLines.indices {
+            guard let localIndex = toLocal[anchor], let remoteIndex = toRemote[anchor],
+                  localIndex >= l, remoteIndex >= r
+            else { continue }
+            appendChunk(base: b..<anchor, local: l..<localIndex, remote: r..<remoteIndex)
+            appendChunk(base: anchor..<anchor + 1, local: localIndex..<localIndex + 1, remote: remoteIndex..<remoteIndex + 1)
+            b = anchor + 1
+            l = localIndex + 1
+            r = remoteIndex + 1
+        }
+        appendChunk(base: b..<baseLines.count, local: l..<localLines.count, remote: r..<remoteLines.count)
+        return chunks
+    }
+
+    /// For each line of `lhs`, the index of the line it pairs with in a shortest edit script to
+    /// `rhs`, or nil when it was removed. Common leading and trailing lines are paired first; the
+    /// rest goes through Myers' O((n + m)·d) search over interned lines.
+    static func matches(_ lhs: [String], _ rhs: [String]) -> [Int?] {
+        var result = [Int?](repeating: nil, count: lhs.count)
+        var prefix = 0
+        while prefix < lhs.count, prefix < rhs.count, lhs[prefix] == rhs[prefix] {
+            result[prefix] = prefix
+            prefix += 1
+        }
+        var suffix = 0
+        while suffix < lhs.count - prefix, suffix < rhs.count - prefix,
+              lhs[lhs.count - 1 - suffix] == rhs[rhs.count - 1 - suffix] {
+            result[lhs.count - 1 - suffix] = rhs.count - 1 - suffix
+            suffix += 1
+        }
+
+        var ids: [String: Int] = [:]
+        func intern(_ lines: ArraySlice<String>) -> [Int] {
+            lines.map { line in
+                if let id = ids[line] { return id }
+                ids[line] = ids.count
+                return ids.count - 1
+            }
+        }
+        let a = intern(lhs[prefix..<(lhs.count - suffix)])
+        let b = intern(rhs[prefix..<(rhs.count - suffix)])
+        for (i, j) in shortestEditPairs(a, b) ?? [] {
+            result[prefix + i] = prefix + j
+        }
+        return result
+    }
+
+    /// Index pairs kept by a shortest edit script from `a` to `b`, or nil when it needs more than
+    /// `maxEdits` insertions and deletions.
+    private static func shortestEditPairs(_ a: [Int], _ b: [Int]) -> [(Int, Int)]? {
+        let n = a.count, m = b.count
+        guard n > 0, m > 0 else { return [] }
+        let limit = min(n + m, maxEdits)
+        let offset = limit + 1
+        // v[offset + k] is the furthest x reached on diagonal k = x - y.
+        var v = [Int](repeating: 0, count: 2 * limit + 3)
+        // trace[d] holds diagonals -d...d as they stood before edit d.
+        var trace: [[Int]] = []
+
+        for d in 0...limit {
+            trace.append(Array(v[(offset - d)...(offset + d)]))
+            for k in stride(from: -d, through: d, by: 2) {
+                var x = k == -d || (k != d && v[offset + k - 1] < v[offset + k + 1])
+                    ? v[offset + k + 1]
+                    : v[offset + k - 1] + 1
+                var y = x - k
+                while x < n, y < m, a[x] == b[y] {
+                    x += 1
+                    y += 1
+                }
+                v[offset + k] = x
+                if x >= n, y >= m {
+                    return backtrack(trace, n: n, m: m)
+                }
+            }
+        }
+        return nil
+    }
+
+    private static func backtrack(_ trace: [[Int]], n: Int, m: Int) -> [(Int, Int)] {
+        var pairs: [(Int, Int)] = []
+        var x = n, y = m
+        for d in stride(from: trace.count - 1, to: 0, by: -1) {
+            let v = trace[d]
+            let k = x - y
+            let previousK = k == -d || (k != d && v[d + k - 1] < v[d + k + 1]) ? k + 1 : k - 1
+            let previousX = v[d + previousK]
+            let previousY = previousX - previousK
+            while x > previousX, y > previousY {
+                x -= 1
+                y -= 1
+                pairs.append((x, y))
+            }
+            x = previousX
+            y = previousY
+        }
+        while x > 0, y > 0 {
+            x -= 1
+            y -= 1
+            pairs.append((x, y))
+        }
+        return pairs
+    }
+}
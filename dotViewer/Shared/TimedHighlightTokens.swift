@@ -0,0 +1,17 @@
+import Foundation
+
+/// Tokens plus how long the round trip to HighlightXPC took, for spotting pathologically slow
+/// file types in the field.
+public struct TimedHighlightTokens: Sendable {
+    public let tokens: [HighlightToken]
+    public let elapsedMicroseconds: UInt64
+
+    public init(tokens: [HighlightToken], elapsed: Duration) {
+        self.tokens = tokens
+        let (seconds, attoseconds) = elapsed.components
+        let micros = seconds * 1_000_000 + attoseconds / 1_000_000_000_000
+        self.elapsedMicroseconds = UInt64(max(micros, 0))
+    }
+
+    public var tokenCount: Int { tokens.count }
+}
@@ -0,0 +1,70 @@
+import XCTest
+@testable import Shared
+
+final class ANSIRendererTests: XCTestCase {
+    private let code = "let x = 1\nx"
+    private let tokens = [
+        HighlightToken(s: 0, e: 3, c: "keyword"),
+        HighlightToken(s: 8, e: 9, c: "number"),
+    ]
+
+    func testTruecolorOutputUsesRGBSequencesAndResets() {
+        let output = ANSIRenderer.render(code: code, tokens: tokens, palette: .atomOneDark, truecolor: true)
+        let keyword = RGBColor(hex: ThemePalette.atomOneDark.keyword)!
+
+        XCTAssertTrue(output.contains("\u{1B}["))
+        XCTAssertTrue(output.hasPrefix("\u{1B}[38;2;\(keyword.red);\(keyword.green);\(keyword.blue)mlet"))
+        XCTAssertTrue(output.contains("1\u{1B}[0m\n"))
+        XCTAssertTrue(output.hasSuffix(ANSIRenderer.reset))
+    }
+
+    func testCRLFLinesEachEndWithReset() {
+        let crlf = "let x = 1\r\nx\r\n"
+        let output = ANSIRenderer.render(code: crlf, tokens: tokens, palette: .atomOneDark, mode: .trueColor)
+
+        XCTAssertEqual(output.components(separatedBy: ANSIRenderer.reset + "\n").count, 3)
+        XCTAssertFalse(output.contains("\r"))
+        XCTAssertFalse(output.contains("\u{240D}"))
+    }
+
+    func testDisablingTruecolorSwitchesTo256ColorSequences() {
+        let truecolor = ANSIRenderer.render(code: code, tokens: tokens, palette: .atomOneDark, truecolor: true)
+        let indexed = ANSIRenderer.render(code: code, tokens: tokens, palette: .atomOneDark, truecolor: false)
+
+        XCTAssertNotEqual(truecolor, indexed)
+        XCTAssertTrue(indexed.contains("\u{1B}[38;5;"))
+        XCTAssertFalse(indexed.contains("\u{1B}[38;2;"))
+    }
+
+    func testXterm256Approximation() {
+        XCTAssertEqual(ANSIRenderer.xterm256Index(for: RGBColor(red: 255, green: 0, blue: 0)), 196)
+        XCTAssertEqual(ANSIRenderer.xterm256Index(for: RGBColor(red: 128, green: 128, blue: 128)), 244)
+    }
+
+    func testAnsi16LimitsDistinctColors() {
+        let palettes: [ThemePalette] = [.atomOneDark, .atomOneLight, .githubDark, .solarizedLight, .tokyoNight]
+        var indices = Set<Int>()
+        for palette in palettes {
+            for token in TokenType.allCases {
+                let color = RGBColor(hex: palette.hex(for: token))!
+                indices.insert(ANSIRenderer.colorIndex(for: color, mode: .ansi16)!)
+            }
+        }
+
+        XCTAssertLessThanOrEqual(indices.count, 16)
+        XCTAssertTrue(indices.allSatisfy { (0..<16).contains($0) })
+    }
+
+    func testAnsi16MapsKeywordToNearestHue() {
+        let keyword = RGBColor(hex: ThemePalette.atomOneDark.keyword)!
+        XCTAssertEqual(ANSIRenderer.ansi16Index(for: keyword), 13)
+        XCTAssertEqual(ANSIRenderer.foregroundSequence(for: keyword, mode: .ansi16), "\u{1B}[95m")
+        XCTAssertNil(ANSIRenderer.colorIndex(for: keyword, mode: .trueColor))
+    }
+
+    func testAnsi16OutputUsesBasicSGRCodes() {
+        let output = ANSIRenderer.render(code: code, tokens: tokens, palette: .atomOneDark, mode: .ansi16)
+        XCTAssertFalse(output.contains("38;"))
+        XCTAssertTrue(output.hasSuffix(ANSIRenderer.reset))
+    }
+}
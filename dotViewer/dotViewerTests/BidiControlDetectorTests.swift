@@ -0,0 +1,25 @@
+import XCTest
+@testable import Shared
+
+final class BidiControlDetectorTests: XCTestCase {
+    func testRightToLeftOverrideIsFlagged() {
+        let text = "let ok = true\nif access_level != \"user\u{202E} \u{2066}// Check if admin\u{2069} \u{2066}\" {"
+
+        let matches = BidiControlDetector.detect(in: text)
+
+        XCTAssertTrue(BidiControlDetector.containsBidiControls(text))
+        XCTAssertEqual(matches.first?.line, 2)
+        XCTAssertEqual(matches.first?.column, 24)
+        XCTAssertEqual(matches.first?.abbreviation, "RLO")
+        XCTAssertEqual(matches.map(\.kind), Array(repeating: .bidiControl, count: 4))
+    }
+
+    func testZeroWidthSpaceIsFlaggedButEmojiJoinerAndLeadingBOMAreNot() {
+        let text = "\u{FEFF}pass\u{200B}word = \"👩\u{200D}💻\""
+
+        let matches = BidiControlDetector.detect(in: text)
+
+        XCTAssertEqual(matches, [InvisibleCharacterMatch(line: 1, column: 5, scalar: "\u{200B}", kind: .zeroWidth)])
+        XCTAssertFalse(BidiControlDetector.containsBidiControls(text))
+    }
+}
@@ -0,0 +1,48 @@
+import XCTest
+@testable import Shared
+
+final class BracketPairColorizerTests: XCTestCase {
+    private let depthColors = ["#FFD700", "#DA70D6", "#179FFF"]
+    private let errorColor = "#FF0000"
+
+    func testNestedPairsGetColorsByDepth() {
+        let code = "f(a[0], { b })"
+
+        let colors = BracketPairColorizer.colors(in: code, tokens: [], depthColors: depthColors, errorColor: errorColor)
+
+        XCTAssertEqual(colors[1], "#FFD700")
+        XCTAssertEqual(colors[13], "#FFD700")
+        XCTAssertEqual(colors[3], "#DA70D6")
+        XCTAssertEqual(colors[5], "#DA70D6")
+        XCTAssertEqual(colors[8], "#DA70D6")
+        XCTAssertNotEqual(colors[1], colors[3])
+    }
+
+    func testUnmatchedAndQuotedBrackets() {
+        let code = "a) \"(\" (b]"
+        let tokens = [HighlightToken(s: 3, e: 6, c: "string")]
+
+        let colors = BracketPairColorizer.colors(in: code, tokens: tokens, depthColors: depthColors, errorColor: errorColor)
+
+        XCTAssertEqual(colors[1], errorColor)
+        XCTAssertNil(colors[4])
+        XCTAssertEqual(colors[7], errorColor)
+        XCTAssertEqual(colors[9], errorColor)
+    }
+
+    func testPalettedOverlayRecolorsOnlyBrackets() {
+        let code = "(x)"
+        let palette = ThemePalette.atomOneDark
+
+        let paletted = BracketPairColorizer.paletted(
+            in: code,
+            tokens: [],
+            palette: palette,
+            depthColors: depthColors,
+            errorColor: errorColor
+        )
+
+        XCTAssertEqual(paletted.runs.map(\.text), ["(", "x", ")"])
+        XCTAssertEqual(paletted.runs.map { paletted.color(of: $0) }, ["#FFD700", palette.text.uppercased(), "#FFD700"])
+    }
+}
@@ -0,0 +1,14 @@
+import XCTest
+@testable import Shared
+
+final class BuildInfoTests: XCTestCase {
+    func testCurrentReportsVersionAndCounts() {
+        let info = BuildInfo.current()
+
+        XCTAssertFalse(info.version.isEmpty)
+        XCTAssertEqual(info.languageCount, HighlightLanguage.all.count)
+        XCTAssertEqual(info.themeCount, ThemePalette.selectableThemes.count)
+        XCTAssertLessThanOrEqual(info.treeSitterGrammarCount, info.languageCount)
+        XCTAssertTrue(info.summary.contains(info.version))
+    }
+}
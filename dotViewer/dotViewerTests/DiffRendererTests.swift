@@ -0,0 +1,76 @@
+import XCTest
+@testable import Shared
+
+final class DiffRendererTests: XCTestCase {
+    private let diff = """
+    --- a/main.swift
+    +++ b/main.swift
+    @@ -1,2 +1,2 @@
+     import Foundation
+    -let a = 1
+    +let a = 2
+    """
+
+    func testClassifiesDiffLines() {
+        XCTAssertEqual(DiffRenderer.kind(of: "+++ b/main.swift"), .fileHeader)
+        XCTAssertEqual(DiffRenderer.kind(of: "@@ -1 +1 @@"), .hunkHeader)
+        XCTAssertEqual(DiffRenderer.kind(of: "+let a = 2"), .addition)
+        XCTAssertEqual(DiffRenderer.kind(of: "-let a = 1"), .removal)
+        XCTAssertEqual(DiffRenderer.kind(of: " import Foundation"), .context)
+    }
+
+    func testHeaderLookalikesInsideHunkStayContent() {
+        let diff = """
+        --- a/query.sql
+        +++ b/query.sql
+        @@ -1,2 +1,2 @@
+        --- x
+        +++ y
+         select 1;
+        --- a/next.sql
+        +++ b/next.sql
+        """
+
+        XCTAssertEqual(DiffRenderer.kinds(in: diff), [.fileHeader, .fileHeader, .hunkHeader, .removal, .addition, .context, .fileHeader, .fileHeader])
+        XCTAssertEqual(DiffRenderer.payloadText(in: diff), "\n\n\n-- x\n++ y\nselect 1;\n\n")
+
+        let html = DiffRenderer.renderHTML(text: diff, highlightedPayload: nil, showLineNumbers: false)
+        XCTAssertTrue(html.contains("diff-line diff-del\"><span class=\"code-line\"><span class=\"diff-marker\">-</span>-- x</span>"))
+        XCTAssertTrue(html.contains("diff-line diff-add\"><span class=\"code-line\"><span class=\"diff-marker\">+</span>++ y</span>"))
+    }
+
+    func testPayloadLanguageComesFromPatchedPath() {
+        XCTAssertEqual(DiffRenderer.payloadLanguage(in: diff), "swift")
+    }
+
+    func testPayloadStripsPrefixesAndBlanksHeaders() {
+        XCTAssertEqual(DiffRenderer.payloadText(in: diff), "\n\n\nimport Foundation\nlet a = 1\nlet a = 2")
+    }
+
+    func testAddedLinesGetGreenTintAndHunkHeadersDiffer() {
+        let payload = ["", "", "", "import Foundation", "let a = 1", "<span class=\"tok-keyword\">let</span> a = 2"]
+        let html = DiffRenderer.renderHTML(text: diff, highlightedPayload: payload, showLineNumbers: false)
+
+        XCTAssertTrue(html.contains("<div class=\"line diff-line diff-add\"><span class=\"code-line\"><span class=\"diff-marker\">+</span><span class=\"tok-keyword\">let</span> a = 2</span></div>"))
+        XCTAssertTrue(html.contains("diff-line diff-hunk\"><span class=\"code-line\">@@ -1,2 +1,2 @@"))
+        XCTAssertTrue(html.contains("diff-line diff-context\">"))
+    }
+
+    func testMisalignedPayloadFallsBackToEscapedText() {
+        let html = DiffRenderer.renderHTML(text: "+<b>", highlightedPayload: ["a", "b"], showLineNumbers: true)
+        XCTAssertEqual(html, "<div class=\"line diff-line diff-add\"><span class=\"ln\">1</span><span class=\"code-line\"><span class=\"diff-marker\">+</span>&lt;b&gt;</span></div>")
+    }
+
+    func testControlCharactersRenderAsPictures() {
+        let html = DiffRenderer.renderHTML(text: "--- a/\u{0}x\n+\u{C}<y>", highlightedPayload: nil, showLineNumbers: false)
+
+        XCTAssertTrue(html.contains("--- a/\u{2400}x"))
+        XCTAssertTrue(html.contains("</span>\u{240C}&lt;y&gt;</span>"))
+        XCTAssertFalse(html.unicodeScalars.contains { $0.value == 0 || $0.value == 0x0C })
+    }
+
+    func testHighlightedLinesUnwrapCodeBlock() {
+        XCTAssertEqual(DiffRenderer.highlightedLines(fromCodeHTML: "<pre class=\"code\"><code>a\n\nb</code></pre>"), ["a", "", "b"])
+        XCTAssertNil(DiffRenderer.highlightedLines(fromCodeHTML: "<div class=\"line\"></div>"))
+    }
+}
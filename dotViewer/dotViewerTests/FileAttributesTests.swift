@@ -97,4 +97,20 @@ final class FileAttributesTests: XCTestCase {
         // 50% control chars — well above 5% threshold
         XCTAssertFalse(attrs!.looksTextual)
     }
+
+    // MARK: - isProbablyBinary
+
+    func testDecodedSourceTextIsNotBinary() {
+        XCTAssertFalse(FileAttributes.isProbablyBinary(text: "fn main() {\n\tprintln!(\"hi\");\n}\n"))
+        XCTAssertFalse(FileAttributes.isProbablyBinary(text: ""))
+    }
+
+    func testNulInDecodedTextIsBinary() {
+        XCTAssertTrue(FileAttributes.isProbablyBinary(text: "PNG\u{0}\u{0}IHDR"))
+    }
+
+    func testLossyDecodedBytesAreBinary() {
+        let lossy = String(decoding: [0x89, 0x50, 0x4E, 0x47, 0xFF, 0xFE, 0xC3, 0x28, 0x01, 0x02], as: UTF8.self)
+        XCTAssertTrue(FileAttributes.isProbablyBinary(text: lossy))
+    }
 }
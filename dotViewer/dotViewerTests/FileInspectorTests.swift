@@ -0,0 +1,32 @@
+import XCTest
+@testable import Shared
+
+final class FileInspectorTests: XCTestCase {
+    func testCountsWithTrailingNewline() {
+        let info = FileInspector.fileInfo(from: "one\ntwo\nthree\n", fileSizeBytes: 14, isTruncated: false)
+
+        XCTAssertEqual(info.lineCount, 3)
+        XCTAssertEqual(info.characterCount, 14)
+    }
+
+    func testCountsWithoutTrailingNewline() {
+        let info = FileInspector.fileInfo(from: "one\ntwo", fileSizeBytes: 7, isTruncated: false)
+
+        XCTAssertEqual(info.lineCount, 2)
+        XCTAssertEqual(info.characterCount, 7)
+    }
+
+    func testCharacterCountUsesUTF16Units() {
+        let info = FileInspector.fileInfo(from: "é😀", fileSizeBytes: 6, isTruncated: false)
+        XCTAssertEqual(info.characterCount, 3)
+    }
+
+    func testLeadingByteOrderMarkIsStripped() {
+        let info = FileInspector.fileInfo(from: "\u{FEFF}let a = 1\nlet b = 2\n", fileSizeBytes: 23, isTruncated: false)
+
+        XCTAssertEqual(info.text.unicodeScalars.first, "l")
+        XCTAssertEqual(info.lineCount, 2)
+        XCTAssertEqual(info.byteOrderMarkLength, 3)
+        XCTAssertEqual(FileInspector.fileInfo(from: "a", fileSizeBytes: 1, isTruncated: false).byteOrderMarkLength, 0)
+    }
+}
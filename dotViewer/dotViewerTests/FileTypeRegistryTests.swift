@@ -60,6 +60,46 @@ final class FileTypeRegistryTests: XCTestCase {
         XCTAssertEqual(registry.highlightLanguage(for: "yml"), "yaml")
     }
 
+    func testFenceAliasesResolveToTreeSitterGrammars() {
+        let grammarIds = Set(HighlightLanguage.all.filter(\.hasTreeSitterGrammar).map(\.id))
+        for alias in ["js", "yml", "py"] {
+            let resolved = FileTypeRegistry.resolveLanguageAlias(alias)
+            XCTAssertNotNil(resolved, "\(alias) should be a known alias")
+            XCTAssertTrue(grammarIds.contains(resolved ?? ""), "\(alias) resolved to \(resolved ?? "nil") without a grammar")
+        }
+        XCTAssertEqual(FileTypeRegistry.resolveLanguageAlias("C++"), "cpp")
+        XCTAssertEqual(FileTypeRegistry.resolveLanguageAlias(" ts "), "typescript")
+        XCTAssertNil(FileTypeRegistry.resolveLanguageAlias("rust"))
+    }
+
+    // MARK: - Highlight Support
+
+    func testHighlightSupportForLanguageNamesAndExtensions() {
+        XCTAssertTrue(registry.isHighlightSupported("rust"))
+        XCTAssertTrue(registry.isHighlightSupported(".py"))
+        XCTAssertTrue(registry.isHighlightSupported("YML"))
+    }
+
+    func testHighlightSupportRejectsUnknownAndPlainText() {
+        XCTAssertFalse(registry.isHighlightSupported("qzxv_not_a_language"))
+        XCTAssertFalse(registry.isHighlightSupported("csv"))
+        XCTAssertFalse(registry.isHighlightSupported(""))
+    }
+
+    // MARK: - Language Extensions
+
+    func testRustLanguageListsRsExtension() {
+        XCTAssertTrue(registry.extensions(forLanguage: "rust").contains("rs"))
+    }
+
+    func testPickerIdsMatchGrammarKeyedTypes() {
+        XCTAssertTrue(registry.extensions(forLanguage: "csharp").contains("cs"))
+    }
+
+    func testUnknownLanguageHasNoExtensions() {
+        XCTAssertEqual(registry.extensions(forLanguage: "qzxv_not_a_language"), [])
+    }
+
     // MARK: - Filename Resolution
 
     func testFilenameResolution() {
@@ -101,6 +141,51 @@ final class FileTypeRegistryTests: XCTestCase {
         XCTAssertEqual(name, "Swift")
     }
 
+    func testDisplayNameAcceptsLeadingDot() {
+        XCTAssertEqual(registry.displayName(for: ".rs"), "Rust")
+        XCTAssertEqual(registry.displayName(for: "py"), "Python")
+        XCTAssertNil(registry.displayName(for: ".definitelynotreal"))
+    }
+
+    func testLanguageLookupIgnoresCaseAndWhitespace() {
+        XCTAssertEqual(HighlightLanguage.named("RUST")?.id, "rust")
+        XCTAssertEqual(HighlightLanguage.named(" Rust ")?.id, "rust")
+        XCTAssertEqual(HighlightLanguage.named("js")?.id, "javascript")
+        XCTAssertNil(HighlightLanguage.named("  "))
+    }
+
+    func testLanguageLookupResolvesAliasesWhoseTargetIsNotAnId() throws {
+        XCTAssertEqual(HighlightLanguage.named("CSharp")?.id, "csharp")
+        XCTAssertEqual(HighlightLanguage.named("cs")?.id, "csharp")
+        XCTAssertEqual(HighlightLanguage.named("C#")?.id, "csharp")
+        XCTAssertEqual(HighlightLanguage.named("ObjectiveC")?.id, "objectivec")
+        XCTAssertEqual(HighlightLanguage.named("objc")?.id, "objectivec")
+        XCTAssertFalse(HighlightLanguage.match(for: "cs").isFallback)
+
+        defer { registry.removeExtensionOverride("csx") }
+        try registry.registerExtensionOverride("csx", language: "cs")
+        XCTAssertEqual(registry.highlightLanguage(for: "csx"), "csharp")
+    }
+
+    func testLanguageMatchReportsFallback() {
+        let rust = HighlightLanguage.match(for: "rust")
+        XCTAssertFalse(rust.isFallback)
+        XCTAssertEqual(rust.language.displayName, "Rust")
+
+        let unknown = HighlightLanguage.match(for: "klingon")
+        XCTAssertTrue(unknown.isFallback)
+        XCTAssertEqual(unknown.language.displayName, "Plain Text")
+    }
+
+    func testPlainTextSentinelsAreIntentional() {
+        for sentinel in ["plaintext", "text", "", " Text "] {
+            let match = HighlightLanguage.match(for: sentinel)
+            XCTAssertEqual(match.language.id, "plaintext", sentinel)
+            XCTAssertFalse(match.isFallback, sentinel)
+        }
+        XCTAssertTrue(HighlightLanguage.match(for: "plain-ish").isFallback)
+    }
+
     // MARK: - Search
 
     func testSearchReturnsResults() {
@@ -121,4 +206,37 @@ final class FileTypeRegistryTests: XCTestCase {
         // Should have at least a few categories populated
         XCTAssertGreaterThan(grouped.keys.count, 3)
     }
+
+    func testLanguageSlugsAreStableAndDistinct() {
+        XCTAssertEqual(registry.languageSlug(for: "C++"), "c-plus-plus")
+        XCTAssertEqual(registry.languageSlug(for: "cpp"), "c-plus-plus")
+        XCTAssertEqual(registry.languageSlug(for: ".cc"), "c-plus-plus")
+        XCTAssertEqual(registry.languageSlug(for: "Objective-C"), "objective-c")
+        XCTAssertEqual(registry.languageSlug(for: "m"), "objective-c")
+        XCTAssertEqual(registry.languageSlug(for: "C#"), "c-sharp")
+        XCTAssertEqual(registry.languageSlug(for: "C++"), registry.languageSlug(for: "C++"))
+        XCTAssertNil(registry.languageSlug(for: "not-a-language"))
+    }
+
+    func testExtensionOverrideWinsOverBuiltIns() throws {
+        defer {
+            registry.removeExtensionOverride("foo")
+            registry.removeExtensionOverride("json")
+        }
+
+        try registry.registerExtensionOverride(".foo", language: "TOML")
+        try registry.registerExtensionOverride("json", language: "yaml")
+
+        XCTAssertEqual(registry.highlightLanguage(for: "foo"), "toml")
+        XCTAssertEqual(registry.highlightLanguage(for: "json"), "yaml")
+        XCTAssertEqual(FileTypeResolution.bestKey(for: URL(fileURLWithPath: "/tmp/settings.foo")), "foo")
+        XCTAssertEqual(FileTypeResolution.highlightLanguage(forFilename: "settings.foo"), "toml")
+    }
+
+    func testExtensionOverrideToUnknownLanguageThrows() {
+        XCTAssertThrowsError(try registry.registerExtensionOverride("foo", language: "klingon")) { error in
+            XCTAssertEqual(error as? FileTypeRegistryError, .unknownLanguage("klingon"))
+        }
+        XCTAssertNil(registry.highlightLanguage(for: "foo"))
+    }
 }
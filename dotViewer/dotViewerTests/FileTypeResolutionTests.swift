@@ -104,6 +104,14 @@ final class FileTypeResolutionTests: XCTestCase {
         XCTAssertEqual(key, "dockerfile")
     }
 
+    func testBareFilenamesResolveToHighlightLanguages() {
+        XCTAssertEqual(FileTypeResolution.highlightLanguage(forFilename: "Dockerfile"), "dockerfile")
+        XCTAssertEqual(FileTypeResolution.highlightLanguage(forFilename: "Makefile"), "make")
+        XCTAssertEqual(FileTypeResolution.highlightLanguage(forFilename: "CMakeLists.txt"), "cmake")
+        XCTAssertEqual(FileTypeResolution.highlightLanguage(forFilename: ".gitignore"), "bash")
+        XCTAssertNil(FileTypeResolution.highlightLanguage(forFilename: "no-such-file-kind"))
+    }
+
     // MARK: - Edge Cases
 
     func testEmptyFileName() {
@@ -133,4 +141,16 @@ final class FileTypeResolutionTests: XCTestCase {
         // Should match yml at minimum
         XCTAssertFalse(key.isEmpty)
     }
+
+    func testFileLanguageUsesExtensionThenShebang() {
+        XCTAssertEqual(
+            FileTypeResolution.highlightLanguage(for: URL(fileURLWithPath: "/tmp/test.rs"), text: "fn main() {}\n"),
+            "rust"
+        )
+        XCTAssertEqual(
+            FileTypeResolution.highlightLanguage(for: URL(fileURLWithPath: "/tmp/run-tests"), text: "#!/usr/bin/env python3\nprint(1)\n"),
+            "python"
+        )
+        XCTAssertNil(FileTypeResolution.highlightLanguage(for: URL(fileURLWithPath: "/tmp/notes"), text: "hello\n"))
+    }
 }
@@ -0,0 +1,45 @@
+import XCTest
+@testable import Shared
+
+final class FoldRegionTreeTests: XCTestCase {
+    func testRustImplContainsMethodNodes() {
+        let code = """
+        impl Counter {
+            fn new() -> Self {
+                Counter { count: 0 }
+            }
+
+            fn increment(&mut self) {
+                self.count += 1;
+            }
+        }
+        """
+
+        let fnStart = "impl Counter {\n    ".utf8.count
+        let tokens = [
+            HighlightToken(s: 0, e: 4, c: "keyword"),
+            HighlightToken(s: 5, e: 12, c: "type"),
+            HighlightToken(s: fnStart, e: fnStart + 2, c: "keyword"),
+            HighlightToken(s: fnStart + 3, e: fnStart + 6, c: "function"),
+        ]
+
+        let tree = FoldRegionTree.tree(in: code, tokens: tokens)
+
+        XCTAssertEqual(tree.count, 1)
+        XCTAssertEqual(tree[0].lines, 1...9)
+        XCTAssertEqual(tree[0].summary.map(\.text), ["impl", " ", "Counter", " {"])
+        XCTAssertEqual(tree[0].summary.map(\.tokenType), [.keyword, nil, .type, nil])
+        XCTAssertEqual(tree[0].children.map(\.lines), [2...4, 6...8])
+        XCTAssertEqual(tree[0].children.map(\.summaryText), ["fn new() -> Self {", "fn increment(&mut self) {"])
+        XCTAssertEqual(tree[0].children[0].summary.prefix(3).map(\.tokenType), [.keyword, nil, .function])
+        XCTAssertTrue(tree[0].children.allSatisfy { $0.children.isEmpty })
+    }
+
+    func testIndentationLanguageRegionsEndAtDedent() {
+        let code = "def a():\n    x = 1\n\n    return x\n\nendpoint = a()\n"
+
+        let tree = FoldRegionTree.tree(in: code, tokens: [])
+
+        XCTAssertEqual(tree, [FoldNode(lines: 1...4, summary: [HighlightRun(tokenType: nil, text: "def a():")], children: [])])
+    }
+}
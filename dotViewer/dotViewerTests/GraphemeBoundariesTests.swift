@@ -0,0 +1,35 @@
+import XCTest
+@testable import Shared
+
+final class GraphemeBoundariesTests: XCTestCase {
+    func testSpansSnapToClusterBoundaries() {
+        let text = "e\u{301}x"
+        let boundaries = GraphemeBoundaries.offsets(in: text)
+
+        XCTAssertEqual(boundaries, [0, 3, 4])
+        let snapped = GraphemeBoundaries.snap([HighlightToken(s: 0, e: 1, c: "keyword"), HighlightToken(s: 1, e: 3, c: "comment")], to: boundaries)
+        XCTAssertEqual(snapped.map(\.s), [0])
+        XCTAssertEqual(snapped.map(\.e), [3])
+    }
+
+    func testRunsKeepEmojiAndCombiningAccentsWhole() {
+        let family = "👨\u{200D}👩\u{200D}👧"
+        let code = family + "cafe\u{301}"
+        let familyBytes = family.utf8.count
+        // Token edges deliberately land inside the emoji sequence and between "e" and its accent.
+        let tokens = [
+            HighlightToken(s: 4, e: familyBytes + 1, c: "string"),
+            HighlightToken(s: familyBytes, e: familyBytes + 4, c: "keyword"),
+        ]
+
+        let runs = HighlightRuns.runs(in: code, tokens: tokens)
+
+        XCTAssertEqual(runs.map(\.text).joined(), code)
+        XCTAssertEqual(runs.first, HighlightRun(tokenType: nil, text: family))
+        XCTAssertEqual(runs.last, HighlightRun(tokenType: .keyword, text: "cafe\u{301}"))
+    }
+
+    func testCRLFKeepsInnerBoundary() {
+        XCTAssertEqual(GraphemeBoundaries.offsets(in: "é\r\n"), [0, 2, 3, 4])
+    }
+}
@@ -0,0 +1,26 @@
+import XCTest
+@testable import Shared
+
+final class HexDumpFormatterTests: XCTestCase {
+    func testRowsShowOffsetHexAndASCII() {
+        let data = Data([0x48, 0x69, 0x00, 0x0A, 0x7F, 0x41])
+
+        let lines = HexDumpFormatter.lines(for: data, bytesPerRow: 4)
+
+        XCTAssertEqual(lines.map(\.number), [1, 2])
+        XCTAssertEqual(lines[0].runs.map(\.text).joined(), "00000000  48 69 00 0a  |Hi..|")
+        XCTAssertEqual(lines[1].runs.map(\.text).joined(), "00000004  7f 41        |.A|")
+        XCTAssertEqual(lines[1].runs.first, HighlightRun(tokenType: .number, text: "00000004"))
+    }
+
+    func testPrintableAndNonPrintableBytesAreStyledDifferently() {
+        let lines = HexDumpFormatter.lines(for: Data([0x48, 0x69, 0x00, 0x0A]))
+
+        XCTAssertTrue(lines[0].runs.contains(HighlightRun(tokenType: .string, text: "Hi")))
+        XCTAssertTrue(lines[0].runs.contains(HighlightRun(tokenType: .comment, text: "..")))
+    }
+
+    func testEmptyDataHasNoRows() {
+        XCTAssertTrue(HexDumpFormatter.lines(for: Data()).isEmpty)
+    }
+}
@@ -0,0 +1,25 @@
+import XCTest
+@testable import Shared
+
+final class HighlightBudgetTests: XCTestCase {
+    func testExhaustedBudgetLatchesDegraded() {
+        let budget = HighlightBudget(limit: .zero)
+
+        XCTAssertTrue(budget.isExhausted())
+        XCTAssertTrue(budget.isDegraded)
+        XCTAssertTrue(budget.isExhausted())
+    }
+
+    func testGenerousBudgetIsNotDegraded() {
+        let budget = HighlightBudget(limit: .seconds(60))
+
+        XCTAssertFalse(budget.isExhausted())
+        XCTAssertFalse(budget.isDegraded)
+    }
+
+    func testDegradedMarkerDoesNotHideDiffPayloadLines() {
+        let html = "<pre class=\"code\"><code>a\nb</code></pre>" + HighlightBudget.degradedMarker
+
+        XCTAssertEqual(DiffRenderer.highlightedLines(fromCodeHTML: html), ["a", "b"])
+    }
+}
@@ -0,0 +1,28 @@
+import XCTest
+@testable import Shared
+
+final class HighlightProbeTests: XCTestCase {
+    func testRustSampleIsDetectedWithThemeBackground() {
+        let probe = HighlightProbe.probe(sample: "fn main() {}\n", nameOrExtension: "rs", theme: "solarizedDark")
+
+        XCTAssertEqual(probe.detectedLanguage, "Rust")
+        XCTAssertFalse(probe.isFallback)
+        XCTAssertEqual(probe.background, ThemePalette.solarizedDark.background)
+        XCTAssertNotNil(RGBColor(hex: probe.background))
+    }
+
+    func testShebangIsUsedWhenNameIsUnknown() {
+        let probe = HighlightProbe.probe(sample: "#!/usr/bin/env python3\nprint(1)\n", nameOrExtension: "", theme: "auto")
+
+        XCTAssertEqual(probe.detectedLanguage, "Python")
+        XCTAssertFalse(probe.isFallback)
+    }
+
+    func testJunkInputReportsFallback() {
+        let probe = HighlightProbe.probe(sample: "\u{1}\u{2}zz", nameOrExtension: "zzqq", theme: "no-such-theme")
+
+        XCTAssertTrue(probe.isFallback)
+        XCTAssertEqual(probe.detectedLanguage, "Plain Text")
+        XCTAssertNotNil(RGBColor(hex: probe.background))
+    }
+}
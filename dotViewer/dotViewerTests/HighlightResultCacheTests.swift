@@ -0,0 +1,62 @@
+import XCTest
+@testable import Shared
+
+final class HighlightResultCacheTests: XCTestCase {
+    func testIdenticalRequestIsServedFromCache() {
+        let cache = HighlightResultCache(capacity: 4)
+        let key = HighlightResultCache.key(code: "let a = 1", language: "swift", theme: "auto", showLineNumbers: true)
+
+        XCTAssertNil(cache.html(forKey: key))
+        cache.store("<span>let</span>", forKey: key)
+
+        let again = HighlightResultCache.key(code: "let a = 1", language: "swift", theme: "auto", showLineNumbers: true)
+        XCTAssertEqual(again, key)
+        XCTAssertEqual(cache.html(forKey: again), "<span>let</span>")
+        XCTAssertEqual(cache.hitCount, 1)
+        XCTAssertNotEqual(HighlightResultCache.key(code: "let a = 1", language: "swift", theme: "blackout", showLineNumbers: true), key)
+    }
+
+    func testLeastRecentlyUsedEntryIsEvicted() {
+        let cache = HighlightResultCache(capacity: 2)
+        cache.store("a", forKey: "a")
+        cache.store("b", forKey: "b")
+        _ = cache.html(forKey: "a")
+        cache.store("c", forKey: "c")
+
+        XCTAssertNil(cache.html(forKey: "b"))
+        XCTAssertEqual(cache.html(forKey: "a"), "a")
+
+        cache.setCapacity(1)
+        XCTAssertEqual(cache.count, 1)
+        cache.clear()
+        XCTAssertEqual(cache.count, 0)
+        XCTAssertEqual(cache.hitCount, 0)
+    }
+
+    func testDegradedHighlightIsNotCached() {
+        let cache = HighlightResultCache(capacity: 4)
+        cache.store("<pre>partial</pre>" + HighlightBudget.degradedMarker, forKey: "slow")
+
+        XCTAssertNil(cache.html(forKey: "slow"))
+        XCTAssertEqual(cache.count, 0)
+    }
+
+    func testTotalSizeBoundEvictsOldestEntries() {
+        let cache = HighlightResultCache(capacity: 10, maxBytes: 10)
+        cache.store("aaaa", forKey: "a")
+        cache.store("bbbb", forKey: "b")
+        _ = cache.html(forKey: "a")
+        cache.store("cccc", forKey: "c")
+
+        XCTAssertNil(cache.html(forKey: "b"))
+        XCTAssertEqual(cache.totalBytes, 8)
+
+        cache.store(String(repeating: "x", count: 11), forKey: "huge")
+        XCTAssertNil(cache.html(forKey: "huge"))
+        XCTAssertEqual(cache.count, 2)
+
+        cache.setMaxBytes(4)
+        XCTAssertEqual(cache.count, 1)
+        XCTAssertEqual(cache.html(forKey: "c"), "cccc")
+    }
+}
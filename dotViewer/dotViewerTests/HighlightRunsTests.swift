@@ -0,0 +1,262 @@
+import XCTest
+@testable import Shared
+
+final class HighlightRunsTests: XCTestCase {
+    func testLineRangeKeepsBlockCommentOpenedAboveIt() {
+        let code = "let a = 1\n/* start\nmiddle\nend */\nlet b = 2"
+        let commentStart = code.utf8.count - "/* start\nmiddle\nend */\nlet b = 2".utf8.count
+        let commentEnd = commentStart + "/* start\nmiddle\nend */".utf8.count
+        let tokens = [
+            HighlightToken(s: 0, e: 3, c: "keyword"),
+            HighlightToken(s: commentStart, e: commentEnd, c: "comment"),
+        ]
+
+        let lines = HighlightRuns.lines(in: code, tokens: tokens, range: 3...4)
+
+        XCTAssertEqual(lines.map(\.number), [3, 4])
+        XCTAssertEqual(lines[0].runs, [HighlightRun(tokenType: .comment, text: "middle")])
+        XCTAssertEqual(lines[1].runs, [HighlightRun(tokenType: .comment, text: "end */")])
+    }
+
+    func testLineRangePastEndIsTrimmed() {
+        let lines = HighlightRuns.lines(in: "a\nb\n", tokens: [], range: 2...10)
+        XCTAssertEqual(lines, [HighlightedLine(number: 2, runs: [HighlightRun(tokenType: nil, text: "b")])])
+    }
+
+    func testCRLFLinesSplitLikeDisplayLines() {
+        let code = "// a\r\nlet b\r\nc"
+        // The comment token stops between CR and LF; the break still counts once.
+        let tokens = [HighlightToken(s: 0, e: 5, c: "comment"), HighlightToken(s: 6, e: 9, c: "keyword")]
+
+        let lines = HighlightRuns.lines(in: code, tokens: tokens, range: 1...Int.max)
+
+        XCTAssertEqual(lines.map(\.number), [1, 2, 3])
+        XCTAssertEqual(lines.count, TextLineUtilities.lineCount(in: code))
+        XCTAssertEqual(lines[0].runs, [HighlightRun(tokenType: .comment, text: "// a")])
+        XCTAssertEqual(lines[1].runs.first, HighlightRun(tokenType: .keyword, text: "let"))
+        XCTAssertEqual(HighlightRuns.lines(in: code, tokens: tokens, range: 3...3).map(\.number), [3])
+        XCTAssertEqual(HighlightRuns.lines(in: "a\r\nb\r\n", tokens: [], range: 1...Int.max).count, 2)
+        XCTAssertEqual(HighlightRuns.runs(in: code, tokens: tokens, emitNewlines: false).map(\.text).joined(), "// alet bc")
+    }
+
+    func testAnnotationsTintOnlyTheirLines() {
+        let code = "let a = 1\nlet b = 2\nlet c = 3\n"
+        let tokens = [HighlightToken(s: 10, e: 13, c: "keyword")]
+        let annotations = [
+            LineAnnotation(line: 2, background: "#FF000033"),
+            LineAnnotation(line: 3, background: "#FFCC0033"),
+            LineAnnotation(line: 42, background: "#000000"),
+        ]
+
+        let lines = HighlightRuns.lines(in: code, tokens: tokens, annotations: annotations)
+
+        XCTAssertEqual(lines.map(\.background), [nil, "#FF000033", "#FFCC0033"])
+        XCTAssertEqual(lines[1].runs.first, HighlightRun(tokenType: .keyword, text: "let"))
+    }
+
+    func testJSONRoundTripsHighlightedLines() throws {
+        let code = "let a = \"x\"\n// done"
+        let tokens = [
+            HighlightToken(s: 0, e: 3, c: "keyword"),
+            HighlightToken(s: 8, e: 11, c: "string"),
+            HighlightToken(s: 12, e: 19, c: "comment"),
+        ]
+        let lines = HighlightRuns.lines(in: code, tokens: tokens, annotations: [LineAnnotation(line: 2, background: "#330000")])
+
+        let json = HighlightRuns.jsonString(for: lines)
+        let decoded = try JSONDecoder().decode([HighlightedLine].self, from: Data(json.utf8))
+
+        XCTAssertEqual(decoded, lines)
+        XCTAssertEqual(decoded.flatMap(\.runs).count, lines.flatMap(\.runs).count)
+        XCTAssertFalse(json.contains("\n  "))
+        XCTAssertTrue(json.contains("\"tokenType\":\"keyword\""))
+    }
+
+    func testWrappedLinesRespectWidthAndMarkContinuations() {
+        let code = "let value = \"abcdefghijklmnop\"\nok"
+        let tokens = [HighlightToken(s: 0, e: 3, c: "keyword"), HighlightToken(s: 12, e: 30, c: "string")]
+
+        let rows = HighlightRuns.wrappedLines(in: code, tokens: tokens, maxColumns: 10)
+
+        XCTAssertEqual(rows.map(\.number), [1, 1, 1, 2])
+        XCTAssertEqual(rows.map(\.isContinuation), [false, true, true, false])
+        XCTAssertTrue(rows.allSatisfy { $0.runs.reduce(0) { $0 + $1.text.count } <= 10 })
+        XCTAssertEqual(rows[0].runs.map(\.text).joined(), "let value ")
+        XCTAssertEqual(rows[2].runs, [HighlightRun(tokenType: .string, text: "hijklmnop\"")])
+    }
+
+    func testWrappingNeverSplitsGraphemes() {
+        let family = "👨\u{200D}👩\u{200D}👧"
+        let code = "ab" + family + "e\u{301}cd"
+
+        let rows = HighlightRuns.wrappedLines(in: code, tokens: [], maxColumns: 3)
+
+        // The family emoji is two columns wide, so it no longer fits after "ab".
+        XCTAssertEqual(rows.map { $0.runs.map(\.text).joined() }, ["ab", family + "e\u{301}", "cd"])
+    }
+
+    func testWrappingCountsTabStopsAndWideCharacters() {
+        let tabbed = HighlightRuns.wrappedLines(in: "a\tbcdef", tokens: [], maxColumns: 6, tabWidth: 4)
+        XCTAssertEqual(tabbed.map { $0.runs.map(\.text).joined() }, ["a\tbc", "def"])
+
+        let wide = HighlightRuns.wrappedLines(in: "日本語です", tokens: [], maxColumns: 5)
+        XCTAssertEqual(wide.map { $0.runs.map(\.text).joined() }, ["日本", "語で", "す"])
+        XCTAssertTrue(wide.allSatisfy { TextIndentation.visualWidth(of: $0.runs.map(\.text).joined(), tabWidth: 4) <= 5 })
+    }
+
+    func testPalettedRunsShareColorEntries() {
+        let code = "let a = 1\nlet b = 2"
+        let tokens = [
+            HighlightToken(s: 0, e: 3, c: "keyword"),
+            HighlightToken(s: 8, e: 9, c: "number"),
+            HighlightToken(s: 10, e: 13, c: "keyword"),
+            HighlightToken(s: 18, e: 19, c: "number"),
+        ]
+        let palette = ThemePalette.atomOneDark
+
+        let paletted = HighlightRuns.paletted(in: code, tokens: tokens, palette: palette)
+
+        XCTAssertEqual(paletted.colors.count, Set(paletted.colors).count)
+        XCTAssertEqual(paletted.colors.count, 3)
+        XCTAssertEqual(paletted.runs.map(\.text).joined(), code)
+        XCTAssertEqual(paletted.runs.map { paletted.color(of: $0) }, [
+            palette.keyword, palette.text, palette.number, palette.text,
+            palette.keyword, palette.text, palette.number,
+        ].map { $0.uppercased() })
+    }
+
+    func testRightToLeftCommentIsFlaggedPerLine() {
+        let code = "let a = 1 // שלום עולם\nlet b = 2"
+        let commentStart = "let a = 1 ".utf8.count
+        let tokens = [HighlightToken(s: commentStart, e: commentStart + "// שלום עולם".utf8.count, c: "comment")]
+
+        let lines = HighlightRuns.lines(in: code, tokens: tokens, annotations: [])
+
+        XCTAssertEqual(lines.map(\.containsRightToLeft), [true, false])
+        XCTAssertFalse(HighlightRuns.lines(in: "plain ascii\nonly", tokens: [], annotations: []).contains(where: \.containsRightToLeft))
+        XCTAssertTrue(BidiControlDetector.containsRightToLeftText("مرحبا"))
+    }
+
+    func testRunsWithoutNewlinesKeepOnlyTokenText() {
+        let code = "let a = 1\n\n/* x\ny */\n"
+        let commentStart = "let a = 1\n\n".utf8.count
+        let tokens = [
+            HighlightToken(s: 0, e: 3, c: "keyword"),
+            HighlightToken(s: commentStart, e: commentStart + "/* x\ny */".utf8.count, c: "comment"),
+        ]
+
+        let runs = HighlightRuns.runs(in: code, tokens: tokens, emitNewlines: false)
+
+        XCTAssertFalse(runs.contains { $0.text.contains("\n") })
+        XCTAssertEqual(runs.map(\.text).joined(), code.replacingOccurrences(of: "\n", with: ""))
+        XCTAssertEqual(runs.filter { $0.tokenType == .comment }.map(\.text), ["/* x", "y */"])
+        XCTAssertEqual(HighlightRuns.runs(in: code, tokens: tokens, emitNewlines: true), HighlightRuns.runs(in: code, tokens: tokens))
+    }
+
+    func testLongStringLiteralIsSplitAtMaxSpanLength() {
+        let literal = "\"" + String(repeating: "x", count: 1000) + "\""
+        let code = "let s = " + literal + "\n"
+        let tokens = [
+            HighlightToken(s: 0, e: 3, c: "keyword"),
+            HighlightToken(s: 8, e: 8 + literal.utf8.count, c: "string"),
+        ]
+
+        let runs = HighlightRuns.runs(in: code, tokens: tokens, maxSpanCharacters: 256)
+
+        XCTAssertFalse(runs.contains { $0.text.count > 256 })
+        XCTAssertEqual(runs.filter { $0.tokenType == .string }.map(\.text.count), [256, 256, 256, 234])
+        XCTAssertEqual(HighlightRuns.plainText(of: runs), code)
+        XCTAssertEqual(HighlightRuns.runs(in: code, tokens: tokens, maxSpanCharacters: nil), HighlightRuns.runs(in: code, tokens: tokens))
+    }
+
+    func testTrailingWhitespaceMarkAndStrip() {
+        let code = "let a = 1  \t\n// note \nok"
+        let tokens = [
+            HighlightToken(s: 0, e: 3, c: "keyword"),
+            HighlightToken(s: 13, e: 21, c: "comment"),
+        ]
+
+        let marked = HighlightRuns.lines(in: code, tokens: tokens, trailingWhitespace: .mark)
+        XCTAssertEqual(marked[0].runs.last, HighlightRun(tokenType: nil, text: "  \t", background: HighlightRuns.trailingWhitespaceBackground))
+        XCTAssertEqual(marked[1].runs, [
+            HighlightRun(tokenType: .comment, text: "// note"),
+            HighlightRun(tokenType: .comment, text: " ", background: HighlightRuns.trailingWhitespaceBackground),
+        ])
+        XCTAssertEqual(marked[2].runs, [HighlightRun(tokenType: nil, text: "ok")])
+
+        let stripped = HighlightRuns.lines(in: code, tokens: tokens, trailingWhitespace: .strip)
+        XCTAssertEqual(stripped.map { $0.runs.map(\.text).joined() }, ["let a = 1", "// note", "ok"])
+        XCTAssertTrue(stripped.allSatisfy { $0.runs.allSatisfy { $0.background == nil } })
+
+        XCTAssertEqual(
+            HighlightRuns.lines(in: code, tokens: tokens, trailingWhitespace: .keep),
+            HighlightRuns.lines(in: code, tokens: tokens, range: 1...Int.max)
+        )
+    }
+
+    func testDualRenderSharesRunsAndDiffersInColor() {
+        let code = "let a = \"x\" // c"
+        let tokens = [
+            HighlightToken(s: 0, e: 3, c: "keyword"),
+            HighlightToken(s: 8, e: 11, c: "string"),
+            HighlightToken(s: 12, e: 16, c: "comment"),
+        ]
+        let pair = ThemePalette.themePair(for: "githubAuto")
+
+        let dual = HighlightRuns.dual(
+            in: code,
+            tokens: tokens,
+            light: ThemePalette.palette(for: pair.light, systemIsDark: false),
+            dark: ThemePalette.palette(for: pair.dark, systemIsDark: true)
+        )
+
+        XCTAssertEqual(dual.light.runs.map(\.text), dual.dark.runs.map(\.text))
+        XCTAssertEqual(dual.light.runs.count, HighlightRuns.runs(in: code, tokens: tokens).count)
+        XCTAssertNotEqual(dual.light.runs.map { dual.light.color(of: $0) }, dual.dark.runs.map { dual.dark.color(of: $0) })
+        XCTAssertNotEqual(dual.light.background, dual.dark.background)
+    }
+
+    func testRunLengthStreamDecodesToPalettedRuns() {
+        let code = "let é = \"日本\" // 👍\nlet b = 2"
+        let commentStart = "let é = \"日本\" ".utf8.count
+        let tokens = [
+            HighlightToken(s: 0, e: 3, c: "keyword"),
+            HighlightToken(s: "let é = ".utf8.count, e: commentStart - 1, c: "string"),
+            HighlightToken(s: commentStart, e: commentStart + "// 👍".utf8.count, c: "comment"),
+        ]
+        let paletted = HighlightRuns.paletted(in: code, tokens: tokens, palette: .tokyoNight)
+
+        let encoded = RunLengthStyles(paletted)
+
+        XCTAssertEqual(encoded.text, code)
+        XCTAssertEqual(encoded.stream.count, paletted.runs.count * 2)
+        XCTAssertEqual(encoded.decoded(), paletted)
+    }
+
+    func testDominantColorIsTheMostUsedTokenColor() {
+        let code = "// a long explanatory comment\nlet x = 1\n"
+        let tokens = [
+            HighlightToken(s: 0, e: 29, c: "comment"),
+            HighlightToken(s: 30, e: 33, c: "keyword"),
+            HighlightToken(s: 38, e: 39, c: "number"),
+        ]
+        let palette = ThemePalette.atomOneDark
+        let colors = Set(HighlightRuns.runs(in: code, tokens: tokens).compactMap { $0.tokenType.map { palette.hex(for: $0).uppercased() } })
+
+        let dominant = HighlightRuns.dominantColor(in: code, tokens: tokens, palette: palette)
+
+        XCTAssertNotNil(RGBColor(hex: dominant))
+        XCTAssertTrue(colors.contains(dominant))
+        XCTAssertEqual(dominant, palette.comment.uppercased())
+        XCTAssertEqual(HighlightRuns.dominantColor(in: code, tokens: tokens, palette: palette, maxLines: 0), dominant)
+        XCTAssertEqual(HighlightRuns.dominantColor(in: "plain", tokens: [], palette: palette), palette.accent.uppercased())
+    }
+
+    func testPlainTextRoundTripsTheSource() {
+        let code = "let a = 1\r\n\n\t// é 👍\nend\n"
+        let tokens = [HighlightToken(s: 0, e: 3, c: "keyword"), HighlightToken(s: 13, e: 23, c: "comment")]
+
+        XCTAssertEqual(HighlightRuns.plainText(of: HighlightRuns.runs(in: code, tokens: tokens)), code)
+        XCTAssertEqual(HighlightRuns.paletted(in: code, tokens: tokens, palette: .githubDark).plainText, code)
+    }
+}
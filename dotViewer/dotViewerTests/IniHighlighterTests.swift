@@ -0,0 +1,38 @@
+import XCTest
+@testable import Shared
+
+final class IniHighlighterTests: XCTestCase {
+    func testIniExtensionsResolveToIniLanguage() {
+        let registry = FileTypeRegistry.shared
+        for ext in ["ini", "conf", "properties"] {
+            XCTAssertEqual(registry.highlightLanguage(for: ext), "ini", ext)
+        }
+    }
+
+    func testSectionHeaderAndCommentGetDistinctStyles() {
+        let code = "# settings\n[server]\nport = 8080\n"
+
+        let lines = HighlightRuns.lines(in: code, tokens: IniHighlighter.tokens(in: code), range: 1...3)
+
+        XCTAssertEqual(lines[0].runs, [HighlightRun(tokenType: .comment, text: "# settings")])
+        XCTAssertTrue(lines[1].runs.contains(HighlightRun(tokenType: .type, text: "server")))
+        XCTAssertTrue(lines[2].runs.contains(HighlightRun(tokenType: .property, text: "port")))
+        XCTAssertEqual(lines[2].runs.last, HighlightRun(tokenType: nil, text: " 8080"))
+    }
+
+    func testPropertiesWithoutSections() {
+        let code = "! legacy\n  app.name: demo\n; note"
+
+        let tokens = IniHighlighter.tokens(in: code).map { "\($0.c)@\($0.s)-\($0.e)" }
+
+        XCTAssertEqual(tokens, ["comment@0-8", "property@11-19", "punctuation@19-20", "comment@26-32"])
+    }
+
+    func testCRLFOffsetsStayOnTheirLines() {
+        let code = "; note\r\n[db]\r\nhost=x\r\n"
+
+        let tokens = IniHighlighter.tokens(in: code).map { "\($0.c)@\($0.s)-\($0.e)" }
+
+        XCTAssertEqual(tokens, ["comment@0-6", "punctuation@8-9", "type@9-11", "punctuation@11-12", "property@14-18", "punctuation@18-19"])
+    }
+}
@@ -109,6 +109,27 @@ final class MarkdownRendererTests: XCTestCase {
         XCTAssertTrue(html.contains("language-python"))
     }
 
+    func testFencedCodeBlocksReportLanguageAndRange() {
+        let md = "# Title\n\n```rust {.numberLines}\nfn main() {}\n```\n\n```\nplain\ntext\n```\n"
+        let blocks = MarkdownRenderer.fencedCodeBlocks(in: md)
+
+        XCTAssertEqual(blocks.map(\.language), ["rust", "plaintext"])
+        XCTAssertEqual(blocks.map(\.infoString), ["rust {.numberLines}", ""])
+        XCTAssertEqual(blocks.map(\.code), ["fn main() {}", "plain\ntext"])
+        let bytes = Array(md.utf8)
+        for block in blocks {
+            XCTAssertEqual(String(decoding: bytes[block.codeRange], as: UTF8.self), block.code)
+        }
+    }
+
+    func testUnusualInfoStringsResolveLanguage() {
+        XCTAssertEqual(MarkdownRenderer.codeBlockLanguage(infoString: "{.python}"), "python")
+        XCTAssertEqual(MarkdownRenderer.codeBlockLanguage(infoString: "language-rust"), "rust")
+        XCTAssertEqual(MarkdownRenderer.codeBlockLanguage(infoString: "rs,ignore"), "rust")
+        XCTAssertNil(MarkdownRenderer.codeBlockLanguage(infoString: "notalanguage"))
+        XCTAssertTrue(MarkdownRenderer.renderHTML(from: "```rust,ignore\nx\n```").contains("language-rust"))
+    }
+
     func testCodeBlockEscapesHTML() {
         let md = """
         ```
@@ -296,4 +317,22 @@ final class MarkdownRendererTests: XCTestCase {
         XCTAssertTrue(toc!.contains("Real Heading"))
         XCTAssertFalse(toc!.contains("Not a heading"))
     }
+
+    func testYAMLFrontMatterIsSplitFromBody() throws {
+        let markdown = "---\ntitle: Hello\ntags: [a, b]\n---\n# Body\n"
+
+        let front = try XCTUnwrap(MarkdownRenderer.frontMatter(in: markdown))
+
+        XCTAssertEqual(front.language, "yaml")
+        XCTAssertEqual(front.code, "title: Hello\ntags: [a, b]")
+        let bytes = Array(markdown.utf8)
+        XCTAssertEqual(String(decoding: bytes[front.codeRange], as: UTF8.self), front.code)
+        XCTAssertEqual(String(decoding: bytes[front.bodyRange], as: UTF8.self), "# Body\n")
+    }
+
+    func testTOMLFrontMatterAndMissingFences() {
+        XCTAssertEqual(MarkdownRenderer.frontMatter(in: "+++\ntitle = \"x\"\n+++\nbody")?.language, "toml")
+        XCTAssertNil(MarkdownRenderer.frontMatter(in: "---\ntitle: never closed\n"))
+        XCTAssertNil(MarkdownRenderer.frontMatter(in: "# Heading\n---\n"))
+    }
 }
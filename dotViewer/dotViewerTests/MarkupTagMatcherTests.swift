@@ -0,0 +1,27 @@
+import XCTest
+@testable import Shared
+
+final class MarkupTagMatcherTests: XCTestCase {
+    func testNestedTagsPair() {
+        let pairs = MarkupTagMatcher.pairs(in: "<div><span></span></div>", languageId: "html")
+
+        XCTAssertEqual(pairs, [
+            TagPair(name: "div", open: 0..<5, close: 18..<24),
+            TagPair(name: "span", open: 5..<11, close: 11..<18),
+        ])
+    }
+
+    func testSelfClosingVoidAndCommentedTagsAreSkipped() {
+        let code = "<p><br><img src=\"a>b\"/><!-- <p> --><![CDATA[</p>]]></p>"
+        let pairs = MarkupTagMatcher.pairs(in: code, languageId: "html")
+
+        XCTAssertEqual(pairs.map(\.name), ["p"])
+        XCTAssertEqual(pairs.first?.open, 0..<3)
+        XCTAssertEqual(pairs.first?.close.upperBound, code.utf8.count)
+    }
+
+    func testXMLNamesAreCaseSensitive() {
+        let pairs = MarkupTagMatcher.pairs(in: "<Item></item></Item>", languageId: "xml")
+        XCTAssertEqual(pairs.map(\.close), [13..<20])
+    }
+}
@@ -0,0 +1,34 @@
+import XCTest
+@testable import Shared
+
+final class NotebookParserTests: XCTestCase {
+    private let notebook = """
+    {
+      "cells": [
+        {"cell_type": "code", "metadata": {}, "outputs": [], "source": ["import math\\n", "print(math.pi)"]},
+        {"cell_type": "markdown", "metadata": {}, "source": "# Notes"}
+      ],
+      "metadata": {"kernelspec": {"display_name": "Python 3", "language": "python", "name": "python3"}},
+      "nbformat": 4,
+      "nbformat_minor": 5
+    }
+    """
+
+    func testCodeAndMarkdownCells() throws {
+        let cells = try NotebookParser.cells(from: Data(notebook.utf8))
+
+        XCTAssertEqual(cells, [
+            NotebookCell(cellType: "code", language: "python", source: "import math\nprint(math.pi)"),
+            NotebookCell(cellType: "markdown", language: "markdown", source: "# Notes"),
+        ])
+    }
+
+    func testMalformedNotebookThrows() {
+        XCTAssertThrowsError(try NotebookParser.cells(from: Data("{\"cells\": [".utf8))) { error in
+            XCTAssertEqual(error as? NotebookParserError, .invalidJSON)
+        }
+        XCTAssertThrowsError(try NotebookParser.cells(from: Data("{}".utf8))) { error in
+            XCTAssertEqual(error as? NotebookParserError, .missingCells)
+        }
+    }
+}
@@ -0,0 +1,32 @@
+import XCTest
+@testable import Shared
+
+final class OutlineExtractorTests: XCTestCase {
+    func testRustDeclarations() {
+        let code = """
+        use std::fmt;
+
+        pub struct Point {
+            x: i32,
+        }
+
+        fn main() {
+            let typed = Point { x: 1 };
+        }
+        """
+
+        XCTAssertEqual(OutlineExtractor.items(in: code, languageId: "rust"), [
+            OutlineItem(line: 3, kind: .type, text: "Point"),
+            OutlineItem(line: 7, kind: .function, text: "main"),
+        ])
+    }
+
+    func testMarkdownHeadingsSkipFencedCode() {
+        let markdown = "# Title\n\n```sh\n# not a heading\n```\n## Usage\n#hashtag"
+
+        XCTAssertEqual(OutlineExtractor.items(in: markdown, languageId: "markdown"), [
+            OutlineItem(line: 1, kind: .heading, text: "Title"),
+            OutlineItem(line: 6, kind: .heading, text: "Usage"),
+        ])
+    }
+}
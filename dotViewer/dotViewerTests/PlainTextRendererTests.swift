@@ -0,0 +1,51 @@
+import XCTest
+import Shared
+
+final class PlainTextRendererTests: XCTestCase {
+    func testPlainOutputEscapesWithoutTokenSpans() {
+        let html = PlainTextRenderer.render(code: "if a < b && c > \"d\" {\n  é\n}\n", showLineNumbers: false)
+
+        XCTAssertEqual(html, "<pre class=\"code\"><code>if a &lt; b &amp;&amp; c &gt; &quot;d&quot; {\n  é\n}</code></pre>")
+        XCTAssertFalse(html.contains("tok-"))
+    }
+
+    func testPlainOutputWithLineNumbers() {
+        let html = PlainTextRenderer.render(code: "x\n<y>", showLineNumbers: true)
+
+        XCTAssertEqual(
+            html,
+            "<div class=\"line\"><span class=\"ln\">1</span><span class=\"code-line\">x</span></div>"
+                + "<div class=\"line\"><span class=\"ln\">2</span><span class=\"code-line\">&lt;y&gt;</span></div>"
+        )
+    }
+
+    func testGutterNumbersStartAtRequestedLine() {
+        let code = "let a = 1\nlet b = 2\n"
+        let tokens = [HighlightToken(s: 0, e: 3, c: "keyword"), HighlightToken(s: 10, e: 13, c: "keyword")]
+
+        let html = PlainTextRenderer.render(code: code, tokens: tokens, startNumber: 41)
+
+        XCTAssertEqual(
+            html,
+            "<div class=\"line\"><span class=\"ln\">41</span><span class=\"code-line\"><span class=\"tok-keyword\">let</span> a = 1</span></div>"
+                + "<div class=\"line\"><span class=\"ln\">42</span><span class=\"code-line\"><span class=\"tok-keyword\">let</span> b = 2</span></div>"
+        )
+        XCTAssertTrue(PlainTextRenderer.render(code: "x\ny", showLineNumbers: true, startNumber: 7).contains("<span class=\"ln\">8</span>"))
+        for palette in [ThemePalette.atomOneDark, .githubLight] {
+            XCTAssertNotEqual(palette.gutter, palette.background)
+        }
+    }
+
+    func testControlCharactersBecomeVisiblePictures() {
+        let code = "a\u{0}b\u{C}let x\tz"
+        let letStart = "a\u{0}b\u{C}".utf8.count
+        let tokens = [HighlightToken(s: letStart, e: letStart + 3, c: "keyword")]
+
+        let html = PlainTextRenderer.render(code: code, tokens: tokens)
+
+        XCTAssertFalse(html.unicodeScalars.contains { $0.value < 0x20 && $0 != "\t" })
+        XCTAssertTrue(html.contains("a\u{2400}b\u{240C}<span class=\"tok-keyword\">let</span> x\tz"))
+        XCTAssertEqual(TextLineUtilities.visibleControlCharacters(code).count, code.count)
+        XCTAssertFalse(RTFRenderer.escape(code).unicodeScalars.contains { $0.value == 0 || $0.value == 0x0C })
+    }
+}
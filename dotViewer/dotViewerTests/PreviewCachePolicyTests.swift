@@ -0,0 +1,17 @@
+import XCTest
+@testable import Shared
+
+final class PreviewCachePolicyTests: XCTestCase {
+    func testSmallFileIsCached() {
+        XCTAssertEqual(PreviewCachePolicy.recommended(byteCount: 4_000, lineCount: 120, maxBytes: 100_000), .cache)
+    }
+
+    func testBorderlineFileIsTruncatedThenCached() {
+        XCTAssertEqual(PreviewCachePolicy.recommended(byteCount: 250_000, lineCount: 6_000, maxBytes: 100_000), .truncateThenCache)
+        XCTAssertEqual(PreviewCachePolicy.recommended(byteCount: 90_000, lineCount: 90_000, maxBytes: 100_000), .truncateThenCache)
+    }
+
+    func testHugeFileIsNotCached() {
+        XCTAssertEqual(PreviewCachePolicy.recommended(byteCount: 50_000_000, lineCount: 900_000, maxBytes: 100_000), .noCache)
+    }
+}
@@ -91,6 +91,22 @@ final class PreviewHTMLBuilderTests: XCTestCase {
         XCTAssertTrue(html.contains("max-width: 1440px;"))
     }
 
+    func testSelectionUsesThemeVariable() {
+        let info = makeInfo(
+            codeContentWidthMode: "auto",
+            codeContentCustomMaxWidth: 1280,
+            markdownRenderedWidthMode: "auto",
+            markdownRenderedCustomMaxWidth: 980,
+            renderedHTML: nil
+        )
+
+        let html = PreviewHTMLBuilder.buildHTML(info: info, palette: ThemePalette.tokyoNight)
+
+        XCTAssertTrue(html.contains("--selection: \(ThemePalette.tokyoNight.selectionBackground);"))
+        XCTAssertTrue(html.contains("background: var(--selection);"))
+        XCTAssertFalse(html.contains("rgba(0, 122, 255, 0.2)"))
+    }
+
     func testCustomPreviewFontsApplyToCodeAndRenderedCSS() {
         let info = makeInfo(
             codeContentWidthMode: "auto",
@@ -304,6 +320,74 @@ final class PreviewHTMLBuilderTests: XCTestCase {
         XCTAssertTrue(html.contains("--bg: #0D1117;"))
     }
 
+    func testDiffLineStylesTintAdditionsAndRemovals() {
+        let info = makeInfo(
+            codeContentWidthMode: "auto",
+            codeContentCustomMaxWidth: 1280,
+            markdownRenderedWidthMode: "auto",
+            markdownRenderedCustomMaxWidth: 980,
+            renderedHTML: nil
+        )
+
+        let html = PreviewHTMLBuilder.buildHTML(info: info, palette: ThemePalette.atomOneLight)
+
+        XCTAssertTrue(html.contains(".line.diff-add"))
+        XCTAssertTrue(html.contains("background: rgba(46, 160, 67, 0.15);"))
+        XCTAssertTrue(html.contains(".line.diff-hunk"))
+    }
+
+    func testFocusTokenKeepsCommentsBrightAndDimsCode() {
+        let info = makeInfo(
+            codeContentWidthMode: "auto",
+            codeContentCustomMaxWidth: 1280,
+            markdownRenderedWidthMode: "auto",
+            markdownRenderedCustomMaxWidth: 980,
+            renderedHTML: nil,
+            focusTokenType: .comment
+        )
+
+        let html = PreviewHTMLBuilder.buildHTML(info: info, palette: ThemePalette.atomOneLight)
+
+        XCTAssertTrue(html.contains(".code-view .tok-keyword { color: color-mix(in srgb, var(--keyword) 35%, var(--bg)); }"))
+        XCTAssertTrue(html.contains(".code-view .code-line, .code-view pre.code { color: color-mix(in srgb, var(--text) 35%, var(--bg)); }"))
+        XCTAssertFalse(html.contains("var(--comment) 35%"))
+        XCTAssertTrue(html.contains(".tok-comment { color: var(--comment); }"))
+    }
+
+    func testNoFocusTokenEmitsNoDimmingRules() {
+        let info = makeInfo(
+            codeContentWidthMode: "auto",
+            codeContentCustomMaxWidth: 1280,
+            markdownRenderedWidthMode: "auto",
+            markdownRenderedCustomMaxWidth: 980,
+            renderedHTML: nil
+        )
+
+        let html = PreviewHTMLBuilder.buildHTML(info: info, palette: ThemePalette.atomOneLight)
+
+        XCTAssertFalse(html.contains("35%, var(--bg))"))
+    }
+
+    func testScopeOverrideFontStyleReachesTokenCSS() {
+        let info = makeInfo(
+            codeContentWidthMode: "auto",
+            codeContentCustomMaxWidth: 1280,
+            markdownRenderedWidthMode: "auto",
+            markdownRenderedCustomMaxWidth: 980,
+            renderedHTML: nil,
+            styleOverrides: [
+                ScopeOverride(scope: "comment.line", fontStyle: .italic),
+                ScopeOverride(scope: "builtin", fontStyle: .bold),
+            ]
+        )
+
+        let html = PreviewHTMLBuilder.buildHTML(info: info, palette: ThemePalette.atomOneLight)
+
+        XCTAssertTrue(html.contains(".tok-comment { color: var(--comment); font-weight: normal; font-style: italic; text-decoration: none; }"))
+        XCTAssertTrue(html.contains(".tok-builtin { color: var(--builtin); font-weight: bold; font-style: normal; text-decoration: none; }"))
+        XCTAssertTrue(html.contains(".tok-parameter { color: var(--parameter); font-style: italic; }"))
+    }
+
     private func makeInfo(
         codeContentWidthMode: String,
         codeContentCustomMaxWidth: Int,
@@ -315,7 +399,9 @@ final class PreviewHTMLBuilderTests: XCTestCase {
         themeName: String = "atomOneLight",
         renderedHTML: String?,
         codeFontFamilyName: String = PreviewFontFamily.defaultCodeFamily,
-        markdownRenderedFontFamilyName: String = PreviewFontFamily.defaultMarkdownRenderedFamily
+        markdownRenderedFontFamilyName: String = PreviewFontFamily.defaultMarkdownRenderedFamily,
+        focusTokenType: TokenType? = nil,
+        styleOverrides: [ScopeOverride] = []
     ) -> PreviewInfo {
         PreviewInfo(
             title: "test.swift",
@@ -354,7 +440,9 @@ final class PreviewHTMLBuilderTests: XCTestCase {
             copyBehavior: "off",
             showSearchButton: false,
             includeLineNumbersInCopy: false,
-            sourceDirectory: "/tmp"
+            sourceDirectory: "/tmp",
+            focusTokenType: focusTokenType,
+            styleOverrides: styleOverrides
         )
     }
 }
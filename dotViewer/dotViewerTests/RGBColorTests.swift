@@ -0,0 +1,36 @@
+import XCTest
+@testable import Shared
+
+final class RGBColorTests: XCTestCase {
+    func testParsesAndFormatsHex() {
+        let color = RGBColor(hex: "#1a1B26")
+        XCTAssertEqual(color, RGBColor(red: 0x1A, green: 0x1B, blue: 0x26))
+        XCTAssertEqual(color?.hex, "#1A1B26")
+    }
+
+    func testRejectsMalformedHex() {
+        XCTAssertNil(RGBColor(hex: "#12345"))
+        XCTAssertNil(RGBColor(hex: "#GG0000"))
+        XCTAssertNil(RGBColor(hex: ""))
+    }
+
+    func testContrastRatioBounds() {
+        let black = RGBColor(red: 0, green: 0, blue: 0)
+        let white = RGBColor(red: 255, green: 255, blue: 255)
+        XCTAssertEqual(black.contrastRatio(with: white), 21, accuracy: 0.01)
+        XCTAssertEqual(white.contrastRatio(with: white), 1, accuracy: 0.01)
+        XCTAssertTrue(black.isDark)
+        XCTAssertFalse(white.isDark)
+    }
+
+    func testContrastAdjustmentMeetsThresholdWithSmallestShift() {
+        let background = RGBColor(hex: "#FFFFFF")!
+        let faint = RGBColor(hex: "#CCCCCC")!
+
+        let adjusted = faint.adjustedForContrast(against: background, minimumRatio: 4.5)
+
+        XCTAssertGreaterThanOrEqual(adjusted.contrastRatio(with: background), 4.5)
+        XCTAssertLessThan(adjusted.contrastRatio(with: background), 4.7)
+        XCTAssertEqual(background.mixed(with: faint, fraction: 0), background)
+    }
+}
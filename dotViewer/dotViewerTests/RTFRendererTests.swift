@@ -0,0 +1,32 @@
+import XCTest
+@testable import Shared
+
+final class RTFRendererTests: XCTestCase {
+    func testColorTableHasOneEntryPerDistinctColor() {
+        let code = "let a = b\nlet c = 1"
+        let tokens = [
+            HighlightToken(s: 0, e: 3, c: "keyword"),
+            HighlightToken(s: 10, e: 13, c: "keyword"),
+            HighlightToken(s: 18, e: 19, c: "number"),
+        ]
+
+        let rtf = RTFRenderer.render(code: code, tokens: tokens, palette: .atomOneDark, fontName: "Menlo", fontSize: 12)
+
+        let table = rtf.components(separatedBy: "{\\colortbl;").last?.components(separatedBy: "}").first ?? ""
+        XCTAssertEqual(table.components(separatedBy: ";").filter { !$0.isEmpty }.count, 3)
+        XCTAssertTrue(rtf.hasPrefix("{\\rtf1"))
+        XCTAssertTrue(rtf.contains("{\\fonttbl{\\f0\\fmodern Menlo;}}"))
+        XCTAssertTrue(rtf.contains("\\fs24 "))
+        XCTAssertTrue(rtf.contains("{\\cf2\\b let}"))
+        XCTAssertTrue(rtf.hasSuffix("}"))
+    }
+
+    func testSpecialCharactersAreEscaped() {
+        XCTAssertEqual(RTFRenderer.escape("a\\b{c}"), "a\\\\b\\{c\\}")
+        XCTAssertEqual(RTFRenderer.escape("x\ty\nz"), "x\\tab y\\line z")
+        XCTAssertEqual(RTFRenderer.escape("a\rb"), "a\\line b")
+        XCTAssertEqual(RTFRenderer.escape("a\r\nb"), "a\\line b")
+        XCTAssertEqual(RTFRenderer.escape("é"), "\\u233?")
+        XCTAssertEqual(RTFRenderer.escape("😀"), "\\u-10179?\\u-8704?")
+    }
+}
@@ -0,0 +1,78 @@
+import XCTest
+@testable import Shared
+
+final class SearchMatchHighlighterTests: XCTestCase {
+    func testMatchInsideStringLiteralKeepsTokenSpan() {
+        let html = "<span class=\"tok-keyword\">let</span> s = <span class=\"tok-string\">&quot;hello world&quot;</span>"
+
+        let result = SearchMatchHighlighter.highlight(html: html, query: "world", caseSensitive: true)
+
+        XCTAssertEqual(
+            result,
+            "<span class=\"tok-keyword\">let</span> s = <span class=\"tok-string\">&quot;hello <span class=\"search-highlight\">world</span>&quot;</span>"
+        )
+    }
+
+    func testCaseInsensitiveMatch() {
+        let html = "<span class=\"tok-keyword\">let</span> x"
+
+        XCTAssertEqual(SearchMatchHighlighter.highlight(html: html, query: "LET", caseSensitive: true), html)
+        XCTAssertEqual(
+            SearchMatchHighlighter.highlight(html: html, query: "LET", caseSensitive: false),
+            "<span class=\"tok-keyword\"><span class=\"search-highlight\">let</span></span> x"
+        )
+    }
+
+    func testMatchAcrossTokensSplitsAtTagBoundaries() {
+        let html = "a <span class=\"tok-punctuation\">=</span> <span class=\"tok-number\">1</span>"
+
+        let result = SearchMatchHighlighter.highlight(html: html, query: "= 1", caseSensitive: true)
+
+        XCTAssertEqual(
+            result,
+            "a <span class=\"tok-punctuation\"><span class=\"search-highlight\">=</span></span><span class=\"search-highlight\"> </span><span class=\"tok-number\"><span class=\"search-highlight\">1</span></span>"
+        )
+    }
+
+    func testOverlappingMatchesMerge() {
+        XCTAssertEqual(SearchMatchHighlighter.matchRanges(in: "aaa b aa", query: "aa", caseSensitive: true), [0..<3, 6..<8])
+        XCTAssertEqual(SearchMatchHighlighter.matchRanges(in: "a&b", query: "&", caseSensitive: true), [1..<2])
+    }
+
+    func testEntitiesCountAsSingleCharacters() {
+        let result = SearchMatchHighlighter.highlight(html: "a &lt;b&gt; c", query: "<b>", caseSensitive: true)
+        XCTAssertEqual(result, "a <span class=\"search-highlight\">&lt;b&gt;</span> c")
+    }
+
+    func testRandomMultibyteInputAlwaysYieldsValidRanges() {
+        let alphabet: [String] = ["a", "E", "é", "e\u{301}", "ß", "日", "👍", "👨\u{200D}👩\u{200D}👧", "🇳🇴", "\u{301}", "\r\n", "&", "<", " "]
+        var state: UInt64 = 0x2545_F491_4F6C_DD1D
+        func next(_ bound: Int) -> Int {
+            state = state &* 6_364_136_223_846_793_005 &+ 1_442_695_040_888_963_407
+            return Int((state >> 33) % UInt64(bound))
+        }
+
+        for _ in 0..<300 {
+            let text = (0..<next(24)).map { _ in alphabet[next(alphabet.count)] }.joined()
+            let query = (0..<(1 + next(3))).map { _ in alphabet[next(alphabet.count)] }.joined()
+            let caseSensitive = next(2) == 0
+
+            let ranges = SearchMatchHighlighter.matchRanges(in: text, query: query, caseSensitive: caseSensitive)
+
+            var previousEnd = -1
+            for range in ranges {
+                XCTAssertGreaterThan(range.lowerBound, previousEnd, "\(text.debugDescription) / \(query.debugDescription)")
+                XCTAssertLessThanOrEqual(range.upperBound, text.count)
+                XCTAssertFalse(range.isEmpty)
+                previousEnd = range.upperBound
+            }
+
+            let escaped = text.replacingOccurrences(of: "&", with: "&amp;").replacingOccurrences(of: "<", with: "&lt;")
+            let html = SearchMatchHighlighter.highlight(html: escaped, query: query, caseSensitive: caseSensitive)
+            let stripped = html
+                .replacingOccurrences(of: "<span class=\"search-highlight\">", with: "")
+                .replacingOccurrences(of: "</span>", with: "")
+            XCTAssertEqual(stripped, escaped)
+        }
+    }
+}
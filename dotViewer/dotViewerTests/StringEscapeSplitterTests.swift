@@ -0,0 +1,98 @@
+import XCTest
+@testable import Shared
+
+final class StringEscapeSplitterTests: XCTestCase {
+    private func split(
+        _ source: String,
+        _ spans: [HighlightToken],
+        scanBytes: Bool = true,
+        rawQuotes: Set<UInt8> = []
+    ) -> [HighlightToken] {
+        StringEscapeSplitter.split(
+            spans,
+            bytes: Array(source.utf8),
+            isString: { $0.hasPrefix("string") },
+            isEscape: { $0.contains("escape") },
+            scanBytes: scanBytes,
+            rawQuotes: rawQuotes
+        )
+    }
+
+    func testNewlineEscapeGetsItsOwnSpan() {
+        let source = "\"line1\\nline2\""
+
+        let result = split(source, [HighlightToken(s: 0, e: 14, c: "string")])
+
+        XCTAssertEqual(result.map(\.s), [0, 6, 8])
+        XCTAssertEqual(result.map(\.e), [6, 8, 14])
+        XCTAssertEqual(result.map(\.c), ["string", "escape", "string"])
+    }
+
+    func testGrammarEscapeCapturesArePreferred() {
+        let source = "\"a\\tb\""
+        let spans = [
+            HighlightToken(s: 0, e: 6, c: "string"),
+            HighlightToken(s: 2, e: 4, c: "string.escape"),
+        ]
+
+        let result = split(source, spans)
+
+        XCTAssertEqual(result.map(\.c), ["string", "string.escape", "string"])
+    }
+
+    func testNonStringSpansPassThrough() {
+        let spans = [HighlightToken(s: 0, e: 3, c: "comment")]
+        XCTAssertEqual(split("a\\n", spans).map(\.c), ["comment"])
+    }
+
+    func testEscapeRangeLengths() {
+        let bytes = Array("\\xFF \\u{1F600} \\u00e9 \\012 \\\\".utf8)
+        let ranges = StringEscapeSplitter.escapeRanges(in: bytes, within: 0..<bytes.count)
+        XCTAssertEqual(ranges.map(\.count), [4, 9, 6, 4, 2])
+    }
+
+    func testRawAndVerbatimLiteralsAreNotScanned() {
+        for source in [#"r"\d+\n""#, #"r'\n'"#, #"br"\x00""#, ##"r#"\t"#"##, #"@"C:\temp""#, #"$@"C:\temp""#] {
+            let spans = [HighlightToken(s: 0, e: source.utf8.count, c: "string")]
+            XCTAssertEqual(split(source, spans).map(\.c), ["string"], source)
+        }
+        XCTAssertEqual(split(#"f"\n""#, [HighlightToken(s: 0, e: 5, c: "string")]).map(\.c), ["string", "escape", "string"])
+    }
+
+    func testLanguageRawQuotesAreNotScanned() {
+        let source = #"'a\nb'"#
+        let spans = [HighlightToken(s: 0, e: 6, c: "string")]
+
+        XCTAssertEqual(split(source, spans, rawQuotes: StringEscapeSplitter.rawQuotes(forLanguage: "bash")).map(\.c), ["string"])
+        XCTAssertEqual(split(source, spans, rawQuotes: StringEscapeSplitter.rawQuotes(forLanguage: "python")).count, 3)
+        XCTAssertEqual(StringEscapeSplitter.rawQuotes(forLanguage: "go"), [UInt8(ascii: "`")])
+    }
+
+    func testGrammarsWithEscapeQueriesAreNotScanned() {
+        let source = "\"a\\nb\" \"c\\td\""
+        let spans = [
+            HighlightToken(s: 0, e: 6, c: "string"),
+            HighlightToken(s: 7, e: 13, c: "string"),
+            HighlightToken(s: 9, e: 11, c: "string.escape"),
+        ]
+
+        let result = split(source, spans, scanBytes: false)
+
+        XCTAssertEqual(result.map(\.c), ["string", "string", "string.escape", "string"])
+        XCTAssertEqual(result.map(\.s), [0, 7, 9, 11])
+    }
+
+    func testManySpansSplitInOrder() {
+        let literal = "\"a\\nb\" "
+        let source = String(repeating: literal, count: 2_000)
+        let spans = (0..<2_000).flatMap { index -> [HighlightToken] in
+            let start = index * literal.utf8.count
+            return [HighlightToken(s: start, e: start + 6, c: "string"), HighlightToken(s: start + 2, e: start + 4, c: "string.escape")]
+        }
+
+        let result = split(source, spans, scanBytes: false)
+
+        XCTAssertEqual(result.count, 6_000)
+        XCTAssertEqual(result.filter { $0.c == "string.escape" }.map(\.s), (0..<2_000).map { $0 * literal.utf8.count + 2 })
+    }
+}
@@ -0,0 +1,34 @@
+import XCTest
+@testable import Shared
+
+final class StylePolicyCheckerTests: XCTestCase {
+    func testMissingFinalNewlineIsReported() {
+        let policy = StylePolicy(requireFinalNewline: true)
+        let violations = StylePolicyChecker.violations(in: "let a = 1\nlet b = 2", policy: policy)
+
+        XCTAssertEqual(violations, [StyleViolation(line: 2, kind: .missingFinalNewline)])
+    }
+
+    func testFinalNewlinePresentPassesPolicy() {
+        let policy = StylePolicy(requireFinalNewline: true)
+        XCTAssertTrue(StylePolicyChecker.violations(in: "let a = 1\n", policy: policy).isEmpty)
+    }
+
+    func testIndentAndTrailingWhitespaceViolations() {
+        let policy = StylePolicy(indentStyle: .spaces, indentSize: 4, trimTrailingWhitespace: true)
+        let text = "func a() {\n\treturn\n   let x = 1 \n}\n"
+
+        let violations = StylePolicyChecker.violations(in: text, policy: policy)
+
+        XCTAssertEqual(violations, [
+            StyleViolation(line: 2, kind: .wrongIndentStyle),
+            StyleViolation(line: 3, kind: .wrongIndentSize),
+            StyleViolation(line: 3, kind: .trailingWhitespace),
+        ])
+    }
+
+    func testDetectsDominantSpaceIndentation() {
+        let detected = TextIndentation.detect(in: "a\n  b\n    c\n  d\n")
+        XCTAssertEqual(detected, DetectedIndentation(style: .spaces, width: 2))
+    }
+}
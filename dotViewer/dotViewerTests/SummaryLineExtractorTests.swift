@@ -0,0 +1,45 @@
+import XCTest
+@testable import Shared
+
+final class SummaryLineExtractorTests: XCTestCase {
+    func testRustFileReturnsFirstDeclaration() {
+        let code = """
+        // Entry point
+        use std::io;
+
+        pub struct Config {
+            verbose: bool,
+        }
+
+        fn main() {}
+        """
+
+        let structLine = "// Entry point\nuse std::io;\n\n".utf8.count
+        let tokens = [
+            HighlightToken(s: 0, e: 14, c: "comment"),
+            HighlightToken(s: 15, e: 18, c: "keyword"),
+            HighlightToken(s: structLine, e: structLine + 3, c: "keyword"),
+            HighlightToken(s: structLine + 4, e: structLine + 10, c: "keyword"),
+            HighlightToken(s: structLine + 11, e: structLine + 17, c: "type"),
+        ]
+
+        let summary = SummaryLineExtractor.summaryLine(in: code, tokens: tokens, languageId: "rust")
+
+        XCTAssertEqual(summary?.lineNumber, 4)
+        XCTAssertEqual(summary?.text, "pub struct Config {")
+        XCTAssertEqual(summary?.runs.map(\.text), ["pub", " ", "struct", " ", "Config", " {"])
+        XCTAssertEqual(summary?.runs.map(\.tokenType), [.keyword, nil, .keyword, nil, .type, nil])
+    }
+
+    func testMarkdownReturnsFirstHeading() {
+        let markdown = "Intro text\n\n## Not a title\n# dotViewer\n\nBody\n"
+
+        let summary = SummaryLineExtractor.summaryLine(in: markdown, tokens: [], languageId: "markdown")
+
+        XCTAssertEqual(summary, SummaryLine(lineNumber: 4, runs: [HighlightRun(tokenType: nil, text: "# dotViewer")]))
+    }
+
+    func testNoDeclarationReturnsNil() {
+        XCTAssertNil(SummaryLineExtractor.summaryLine(in: "let x = 1\nprint(x)\n", tokens: [], languageId: "swift"))
+    }
+}
@@ -0,0 +1,38 @@
+import XCTest
+@testable import Shared
+
+final class TextBlockDetectorTests: XCTestCase {
+    func testMarkdownHeadingAndTwoParagraphs() {
+        let markdown = "# Title\n\nFirst paragraph\ncontinues here.\n\nSecond paragraph.\n"
+
+        let blocks = TextBlockDetector.blocks(in: markdown, languageId: "markdown").filter { $0.kind != .blank }
+
+        XCTAssertEqual(blocks, [
+            TextBlock(startLine: 1, endLine: 1, kind: .heading),
+            TextBlock(startLine: 3, endLine: 4, kind: .paragraph),
+            TextBlock(startLine: 6, endLine: 6, kind: .paragraph),
+        ])
+    }
+
+    func testMarkdownCodeFenceAndList() {
+        let markdown = "- one\n- two\n\n```swift\n\nlet x = 1\n```\n"
+
+        let blocks = TextBlockDetector.blocks(in: markdown, languageId: "markdown")
+
+        XCTAssertEqual(blocks, [
+            TextBlock(startLine: 1, endLine: 2, kind: .list),
+            TextBlock(startLine: 3, endLine: 3, kind: .blank),
+            TextBlock(startLine: 4, endLine: 7, kind: .code),
+        ])
+    }
+
+    func testPlainTextSplitsOnBlankLines() {
+        let blocks = TextBlockDetector.blocks(in: "# not a heading\nb\n\n\nc\n", languageId: "plaintext")
+
+        XCTAssertEqual(blocks, [
+            TextBlock(startLine: 1, endLine: 2, kind: .paragraph),
+            TextBlock(startLine: 3, endLine: 4, kind: .blank),
+            TextBlock(startLine: 5, endLine: 5, kind: .paragraph),
+        ])
+    }
+}
@@ -0,0 +1,52 @@
+import XCTest
+@testable import Shared
+
+final class TextIndentationTests: XCTestCase {
+    func testDoublyIndentedLineGetsTwoGuides() {
+        let code = "fn main() {\n    if ok {\n        run();\n    }\n}\n"
+
+        let guides = TextIndentation.indentationGuides(in: code, tabWidth: 4)
+
+        XCTAssertEqual(guides.count, 5)
+        XCTAssertEqual(guides[0].columns, [])
+        XCTAssertEqual(guides[1].columns, [0])
+        XCTAssertEqual(guides[2], IndentationGuideLine(line: 3, columns: [0, 4]))
+    }
+
+    func testBlankLinesInheritShallowerNeighbour() {
+        let code = "a {\n\t\tb\n\n\tc\n}\n"
+
+        let guides = TextIndentation.indentationGuides(in: code, tabWidth: 2)
+
+        XCTAssertEqual(guides[1].columns, [0, 2])
+        XCTAssertEqual(guides[2].columns, [0])
+        XCTAssertEqual(guides[3].columns, [0])
+    }
+
+    func testTabsExpandToTabStops() {
+        XCTAssertEqual(TextIndentation.visualIndentWidth(of: "  \tx", tabWidth: 4), 4)
+        XCTAssertEqual(TextIndentation.visualIndentWidth(of: "\t\tx", tabWidth: 4), 8)
+    }
+
+    func testTabStopsReportExpandedColumns() {
+        XCTAssertEqual(TextIndentation.tabStops(in: "\t\tx", tabWidth: 4), [
+            TabStopSpan(line: 1, characterIndex: 0, columns: 0..<4),
+            TabStopSpan(line: 1, characterIndex: 1, columns: 4..<8),
+        ])
+        XCTAssertEqual(TextIndentation.tabStops(in: "a\nab\tc", tabWidth: 4).map(\.columns), [2..<4])
+    }
+
+    func testVisualWidthAdvancesTabsFromStartColumn() {
+        XCTAssertEqual(TextIndentation.visualWidth(of: "\t", tabWidth: 4), 4)
+        XCTAssertEqual(TextIndentation.visualWidth(of: "\t", tabWidth: 4, startColumn: 1), 3)
+        XCTAssertEqual(TextIndentation.visualWidth(of: "\t", tabWidth: 4, startColumn: 4), 4)
+        XCTAssertEqual(TextIndentation.visualWidth(of: "ab\tc", tabWidth: 8, startColumn: 5), 4)
+    }
+
+    func testWideCharactersCountDouble() {
+        XCTAssertEqual(TextIndentation.visualWidth(of: "日本語", tabWidth: 4), 6)
+        XCTAssertEqual(TextIndentation.visualWidth(of: "e\u{301}x", tabWidth: 4), 2)
+        XCTAssertEqual(TextIndentation.lineVisualWidths(in: "漢字\tx\nab", tabWidth: 4), [9, 2])
+        XCTAssertEqual(TextIndentation.tabStops(in: "漢\tx", tabWidth: 4).map(\.columns), [2..<4])
+    }
+}
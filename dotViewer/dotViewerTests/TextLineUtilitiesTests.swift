@@ -21,4 +21,9 @@ final class TextLineUtilitiesTests: XCTestCase {
         XCTAssertEqual(TextLineUtilities.visualLineCount(in: ""), 0)
         XCTAssertTrue(TextLineUtilities.lines(forDisplayFrom: "").isEmpty)
     }
+
+    func testBlankRunsCollapseToSingleRow() {
+        let lines = TextLineUtilities.lines(forDisplayFrom: "a\n\n  \n\t\n\nb\n\nc")
+        XCTAssertEqual(TextLineUtilities.collapsingBlankRuns(lines), ["a", "", "b", "", "c"])
+    }
 }
@@ -0,0 +1,199 @@
+import XCTest
+@testable import Shared
+
+final class TextMateThemeParserTests: XCTestCase {
+    private let minimalTheme = """
+    <?xml version="1.0" encoding="UTF-8"?>
+    <!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+    <plist version="1.0">
+    <dict>
+      <key>name</key><string>Inline Test</string>
+      <key>settings</key>
+      <array>
+        <dict>
+          <key>settings</key>
+          <dict>
+            <key>background</key><string>#101010</string>
+            <key>foreground</key><string>#EEEEEE</string>
+          </dict>
+        </dict>
+        <dict>
+          <key>scope</key><string>comment, punctuation.definition.comment</string>
+          <key>settings</key><dict><key>foreground</key><string>#808080</string></dict>
+        </dict>
+        <dict>
+          <key>scope</key><string>constant</string>
+          <key>settings</key><dict><key>foreground</key><string>#F80</string></dict>
+        </dict>
+        <dict>
+          <key>scope</key><string>keyword.control</string>
+          <key>settings</key><dict><key>foreground</key><string>#CC66FFAA</string></dict>
+        </dict>
+      </array>
+    </dict>
+    </plist>
+    """
+
+    func testInlineThemeProducesPaletteWithoutRegistering() throws {
+        let palette = try TextMateThemeParser.palette(from: Data(minimalTheme.utf8))
+
+        XCTAssertEqual(palette.name, "Inline Test")
+        XCTAssertTrue(palette.isDark)
+        XCTAssertEqual(palette.background, "#101010")
+        XCTAssertEqual(palette.comment, "#808080")
+        XCTAssertEqual(palette.keyword, "#CC66FF")
+        XCTAssertEqual(palette.number, "#FF8800")
+        XCTAssertEqual(palette.function, "#EEEEEE")
+
+        XCTAssertFalse(ThemePalette.selectableThemes.contains { $0.title == palette.name || $0.id == palette.name })
+    }
+
+    func testMatchedSelectorsExplainTokenColors() throws {
+        let theme = try TextMateThemeParser.parse(Data(minimalTheme.utf8))
+
+        XCTAssertEqual(theme.matchedSelectors[.keyword], "keyword.control")
+        XCTAssertEqual(theme.matchedSelectors[.number], "constant")
+        XCTAssertEqual(theme.matchedSelectors[.comment], "comment")
+        XCTAssertNil(theme.matchedSelectors[.function])
+    }
+
+    private func theme(rules: [(scope: String, color: String)]) -> Data {
+        let entries = rules.map { rule in
+            "<dict><key>scope</key><string>\(rule.scope)</string><key>settings</key><dict><key>foreground</key><string>\(rule.color)</string></dict></dict>"
+        }.joined()
+        return Data("""
+        <plist version="1.0"><dict><key>settings</key><array>
+        <dict><key>settings</key><dict><key>background</key><string>#FFFFFF</string></dict></dict>
+        \(entries)
+        </array></dict></plist>
+        """.utf8)
+    }
+
+    func testExactSelectorBeatsEarlierDescendant() throws {
+        let palette = try TextMateThemeParser.palette(from: theme(rules: [("string.quoted", "#111111"), ("string", "#222222")]))
+        XCTAssertEqual(palette.string, "#222222")
+    }
+
+    func testLaterRuleWinsAmongEqualSelectors() throws {
+        let parsed = try TextMateThemeParser.parse(theme(rules: [("comment", "#111111"), ("keyword", "#333333"), ("comment", "#222222")]))
+        XCTAssertEqual(parsed.palette.comment, "#222222")
+        XCTAssertEqual(parsed.matchedSelectors[.comment], "comment")
+    }
+
+    func testLongerAncestorBeatsShorterAndDescendantIsLastResort() throws {
+        let number = try TextMateThemeParser.palette(from: theme(rules: [("constant.numeric.integer", "#444444"), ("constant", "#111111")]))
+        XCTAssertEqual(number.number, "#111111")
+
+        let escape = try TextMateThemeParser.palette(from: theme(rules: [("constant.character", "#555555"), ("constant", "#111111")]))
+        XCTAssertEqual(escape.escape, "#555555")
+
+        let strings = try TextMateThemeParser.palette(from: theme(rules: [("string.quoted.double", "#333333"), ("string.quoted", "#444444")]))
+        XCTAssertEqual(strings.string, "#444444")
+    }
+
+    func testScopesListEverySelectorTheThemeStyles() throws {
+        let theme = try TextMateThemeParser.parse(Data(minimalTheme.utf8))
+
+        XCTAssertEqual(theme.scopes, ["comment", "punctuation.definition.comment", "constant", "keyword.control"])
+        XCTAssertTrue(theme.scopes.contains { $0.hasPrefix("comment") })
+    }
+
+    func testMissingForegroundFollowsBackgroundLuminance() throws {
+        let theme = minimalTheme
+            .replacingOccurrences(of: "<string>#101010</string>", with: "<string>#FAFAFA</string>")
+            .replacingOccurrences(of: "<key>foreground</key><string>#EEEEEE</string>", with: "")
+
+        let palette = try TextMateThemeParser.palette(from: Data(theme.utf8))
+
+        XCTAssertFalse(palette.isDark)
+        XCTAssertEqual(palette.text, "#000000")
+        XCTAssertEqual(palette.function, "#000000")
+        XCTAssertEqual(palette.comment, "#808080")
+    }
+
+    func testTranslucentLineHighlightKeepsAlpha() throws {
+        let theme = minimalTheme.replacingOccurrences(
+            of: "<key>foreground</key><string>#EEEEEE</string>",
+            with: "<key>foreground</key><string>#EEEEEE</string>"
+                + "<key>lineHighlight</key><string>#FFFFFF1A</string>"
+                + "<key>selection</key><string>#FFFFFF1B</string>"
+        )
+
+        let parsed = try TextMateThemeParser.parse(Data(theme.utf8))
+
+        XCTAssertEqual(parsed.lineHighlight, "#FFFFFF1A")
+        XCTAssertEqual(parsed.selection, "#FFFFFF1B")
+        XCTAssertNotEqual(parsed.lineHighlight, parsed.selection)
+        XCTAssertEqual(RGBAColor(hex: "#336699FF")?.hex, "#336699")
+    }
+
+    func testParsedThemeIsListedAsCustom() throws {
+        let theme = try TextMateThemeParser.parse(Data(minimalTheme.utf8))
+
+        let themes = ThemePalette.availableThemes(customThemes: [theme])
+
+        XCTAssertEqual(themes.last, ThemeInfo(id: "Inline Test", title: "Inline Test", isBuiltIn: false))
+        XCTAssertEqual(themes.first { $0.id == "atomOneDark" }?.isBuiltIn, true)
+        XCTAssertTrue(ThemePalette.isBuiltInTheme("Solarized Dark"))
+        XCTAssertFalse(ThemePalette.isBuiltInTheme(theme.palette.name))
+    }
+
+    func testDirectoryLoadSkipsInvalidThemes() throws {
+        let directory = FileManager.default.temporaryDirectory.appendingPathComponent(UUID().uuidString)
+        try FileManager.default.createDirectory(at: directory, withIntermediateDirectories: true)
+        defer { try? FileManager.default.removeItem(at: directory) }
+        try Data(minimalTheme.utf8).write(to: directory.appendingPathComponent("valid.tmTheme"))
+        try Data("<plist>broken".utf8).write(to: directory.appendingPathComponent("broken.tmTheme"))
+        try Data(minimalTheme.utf8).write(to: directory.appendingPathComponent("notes.txt"))
+
+        let themes = try TextMateThemeParser.themes(inDirectory: directory)
+
+        XCTAssertEqual(themes.map(\.palette.name), ["Inline Test"])
+    }
+
+    func testMissingDirectoryThrows() {
+        let missing = FileManager.default.temporaryDirectory.appendingPathComponent(UUID().uuidString)
+        XCTAssertThrowsError(try TextMateThemeParser.themes(inDirectory: missing))
+    }
+
+    func testVSCodeThemeMapsEditorColorsAndTokenColors() throws {
+        let json = """
+        {
+          // VS Code themes are JSON with comments.
+          "name": "VS Code Test",
+          "type": "dark",
+          "colors": { "editor.background": "#1B1B1F", "editor.foreground": "#D0D0D0", "activityBar.background": "#000000" },
+          "tokenColors": [
+            { "scope": ["keyword.control", "storage"], "settings": { "foreground": "#C586C0" } },
+            { "scope": "comment", "settings": { "foreground": "#6A9955", "fontStyle": "italic" } },
+            { "name": "no settings" },
+          ],
+          "semanticHighlighting": true
+        }
+        """
+
+        let theme = try TextMateThemeParser.parseVSCodeTheme(Data(json.utf8))
+
+        XCTAssertEqual(theme.palette.name, "VS Code Test")
+        XCTAssertEqual(theme.palette.background, "#1B1B1F")
+        XCTAssertEqual(theme.palette.text, "#D0D0D0")
+        XCTAssertEqual(theme.palette.keyword, "#C586C0")
+        XCTAssertEqual(theme.palette.comment, "#6A9955")
+        XCTAssertTrue(theme.palette.isDark)
+        XCTAssertEqual(theme.scopes, ["keyword.control", "storage", "comment"])
+    }
+
+    func testVSCodeThemeWithoutEditorBackgroundUsesTypeDefault() throws {
+        let theme = try TextMateThemeParser.parseVSCodeTheme(Data(#"{"type": "light", "tokenColors": []}"#.utf8))
+        XCTAssertEqual(theme.palette.background, "#FFFFFF")
+        XCTAssertThrowsError(try TextMateThemeParser.parseVSCodeTheme(Data("[1, 2]".utf8))) { error in
+            XCTAssertEqual(error as? TextMateThemeError, .invalidJSON)
+        }
+    }
+
+    func testInvalidThemeThrows() {
+        XCTAssertThrowsError(try TextMateThemeParser.palette(from: Data("not a plist".utf8))) { error in
+            XCTAssertEqual(error as? TextMateThemeError, .invalidPropertyList)
+        }
+    }
+}
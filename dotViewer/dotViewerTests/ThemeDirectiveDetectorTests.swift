@@ -0,0 +1,28 @@
+import XCTest
+@testable import Shared
+
+final class ThemeDirectiveDetectorTests: XCTestCase {
+    func testDirectiveSelectsLightThemeBackground() {
+        let code = "// dotviewer-theme: solarizedLight\nfn main() {}\n"
+
+        let theme = ThemeDirectiveDetector.theme(in: code)
+        let palette = ThemePalette.palette(for: theme ?? "auto", systemIsDark: true)
+
+        XCTAssertEqual(theme, "solarizedLight")
+        XCTAssertEqual(palette.background, ThemePalette.solarizedLight.background)
+    }
+
+    func testBlockCommentAndCaseInsensitiveId() {
+        XCTAssertEqual(ThemeDirectiveDetector.theme(in: "/* dotviewer-theme: TokyoNight */\n"), "tokyoNight")
+        XCTAssertEqual(ThemeDirectiveDetector.theme(in: "<!-- dotviewer-theme: blackout -->\n"), "blackout")
+    }
+
+    func testDirectiveOutsideCommentOrTooLateIsIgnored() {
+        XCTAssertNil(ThemeDirectiveDetector.theme(in: "let s = \"dotviewer-theme: blackout\"\n"))
+        XCTAssertNil(ThemeDirectiveDetector.theme(in: "a\nb\nc\nd\ne\n# dotviewer-theme: blackout\n"))
+    }
+
+    func testUnknownThemeIsIgnored() {
+        XCTAssertNil(ThemeDirectiveDetector.theme(in: "# dotviewer-theme: neon\n"))
+    }
+}
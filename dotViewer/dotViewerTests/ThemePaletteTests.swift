@@ -84,8 +84,93 @@ final class ThemePaletteTests: XCTestCase {
         XCTAssertTrue(ThemePalette.blackout.isDark)
     }
 
+    func testDeclaredDarknessMatchesBackgroundLuminance() {
+        for theme in Self.allThemes {
+            XCTAssertEqual(theme.backgroundIsDark, theme.isDark, "\(theme.name) isDark disagrees with its background")
+        }
+    }
+
+    func testCaretColorContrastsWithBackground() {
+        for theme in Self.allThemes {
+            let caret = RGBColor(hex: theme.caretColor)
+            XCTAssertNotNil(caret, theme.name)
+            let background = RGBColor(hex: theme.background)!
+            XCTAssertGreaterThanOrEqual(caret?.contrastRatio(with: background) ?? 0, 3, theme.name)
+        }
+    }
+
+    func testSimplifiedPaletteUsesAtMostFiveColors() {
+        for theme in Self.allThemes {
+            let simplified = theme.simplified()
+            let colors = Set(TokenType.allCases.map { simplified.hex(for: $0) } + [simplified.text])
+
+            XCTAssertLessThanOrEqual(colors.count, 5, theme.name)
+            XCTAssertNotEqual(simplified.hex(for: .keyword), simplified.hex(for: .string), theme.name)
+            XCTAssertEqual(simplified.comment, theme.comment, theme.name)
+        }
+    }
+
+    func testMinimumContrastAdjustsOnlyFailingColors() {
+        let theme = ThemePalette.atomOneDark
+        let background = RGBColor(hex: theme.background)!
+        XCTAssertLessThan(RGBColor(hex: theme.comment)!.contrastRatio(with: background), 4.5)
+
+        let adjusted = theme.withMinimumContrast(4.5)
+
+        XCTAssertNotEqual(adjusted.comment, theme.comment)
+        XCTAssertGreaterThanOrEqual(RGBColor(hex: adjusted.comment)!.contrastRatio(with: background), 4.5)
+        XCTAssertEqual(adjusted.text, theme.text)
+        XCTAssertEqual(adjusted.background, theme.background)
+    }
+
+    func testSelectionBackgroundIsTranslucentAccent() {
+        for theme in Self.allThemes {
+            let selection = theme.selectionBackground
+            XCTAssertTrue(selection.hasPrefix("rgba("), theme.name)
+            XCTAssertNotEqual(selection, theme.background, theme.name)
+        }
+        let accent = RGBColor(hex: ThemePalette.atomOneDark.accent)!
+        XCTAssertEqual(ThemePalette.atomOneDark.selectionBackground, "rgba(\(accent.red), \(accent.green), \(accent.blue), 0.35)")
+    }
+
+    func testBackgroundOverrideReplacesOnlyBackground() throws {
+        let palette = try ThemePalette.atomOneDark.withBackground("#000000")
+
+        XCTAssertEqual(palette.background, "#000000")
+        XCTAssertEqual(palette.keyword, ThemePalette.atomOneDark.keyword)
+        XCTAssertTrue(palette.isDark)
+        XCTAssertFalse(try ThemePalette.atomOneDark.withBackground("#fafafa").isDark)
+    }
+
+    func testInvalidBackgroundOverrideThrows() {
+        for value in ["000000", "#12345", "#GGGGGG", "red"] {
+            XCTAssertThrowsError(try ThemePalette.atomOneDark.withBackground(value)) { error in
+                XCTAssertEqual(error as? ThemePaletteError, .invalidHexColor(value))
+            }
+        }
+    }
+
+    func testFixedThemesGroupByLuminance() {
+        let dark = ThemePalette.fixedThemes(isDark: true).map(\.id)
+        let light = ThemePalette.fixedThemes(isDark: false).map(\.id)
+        XCTAssertTrue(dark.contains("solarizedDark"))
+        XCTAssertTrue(light.contains("githubLight"))
+        XCTAssertTrue(Set(dark).isDisjoint(with: light))
+        XCTAssertFalse(dark.contains("auto"))
+    }
+
     // MARK: - Palette Selection
 
+    func testThemeBackgroundsCoverEverySelectableTheme() {
+        let backgrounds = ThemePalette.themeBackgrounds(systemIsDark: true)
+
+        XCTAssertEqual(backgrounds.count, ThemePalette.selectableThemes.count)
+        XCTAssertEqual(backgrounds.map(\.id), ThemePalette.selectableThemes.map(\.id))
+        for entry in backgrounds {
+            XCTAssertNotNil(RGBColor(hex: entry.background), "\(entry.id) background is not valid hex")
+        }
+    }
+
     func testPaletteForAutoThemeDark() {
         let palette = ThemePalette.palette(for: "auto", systemIsDark: true)
         XCTAssertTrue(palette.isDark)
@@ -122,6 +207,13 @@ final class ThemePaletteTests: XCTestCase {
         XCTAssertEqual(palette.name, ThemePalette.solarizedLight.name)
     }
 
+    func testSystemThemesResolveBackgroundToAppearance() {
+        for theme in ["auto", "githubAuto", "xcodeAuto", "solarizedAuto"] {
+            XCTAssertTrue(ThemePalette.palette(for: theme, systemIsDark: true).backgroundIsDark, theme)
+            XCTAssertFalse(ThemePalette.palette(for: theme, systemIsDark: false).backgroundIsDark, theme)
+        }
+    }
+
     func testPaletteForSpecificTheme() {
         for name in Self.themeNames {
             let palette = ThemePalette.palette(for: name, systemIsDark: false)
@@ -144,12 +236,45 @@ final class ThemePaletteTests: XCTestCase {
         XCTAssertEqual(palette.name, ThemePalette.atomOneDark.name)
     }
 
+    func testUnknownThemeUsesRequestedFallback() {
+        let palette = ThemePalette.palette(for: "nonexistent", systemIsDark: true, fallbackTheme: "solarizedLight")
+        XCTAssertFalse(palette.isDark)
+        XCTAssertEqual(palette.background, ThemePalette.solarizedLight.background)
+    }
+
+    func testUnknownFallbackUsesDefault() {
+        let palette = ThemePalette.palette(for: "nonexistent", systemIsDark: false, fallbackTheme: "alsoMissing")
+        XCTAssertEqual(palette.name, ThemePalette.atomOneLight.name)
+    }
+
+    func testThemeNamesResolveCaseAndWhitespaceInsensitively() {
+        XCTAssertEqual(ThemePalette.canonicalThemeId("TokyoNight"), "tokyoNight")
+        XCTAssertEqual(ThemePalette.canonicalThemeId(" Solarized Dark "), "solarizedDark")
+        XCTAssertNil(ThemePalette.canonicalThemeId("Nope"))
+        XCTAssertEqual(ThemePalette.palette(for: "GITHUBDARK", systemIsDark: false).name, ThemePalette.githubDark.name)
+        XCTAssertTrue(ThemePalette.followsSystemAppearance(theme: " XcodeAuto"))
+    }
+
+    func testKnownThemeIgnoresFallback() {
+        let palette = ThemePalette.palette(for: "tokyoNight", systemIsDark: false, fallbackTheme: "githubLight")
+        XCTAssertEqual(palette.name, ThemePalette.tokyoNight.name)
+    }
+
     // MARK: - TokenType Enum
 
     func testTokenTypeAllCasesNotEmpty() {
         XCTAssertGreaterThan(TokenType.allCases.count, 10)
     }
 
+    func testFontStyleBitmaskDecodesToFlags() {
+        let boldUnderline = FontStyleFlags(bitmask: 5)
+        XCTAssertEqual(boldUnderline, FontStyleFlags(isBold: true, isUnderline: true))
+        XCTAssertFalse(boldUnderline.isItalic)
+        XCTAssertEqual(FontStyleFlags(isBold: true, isItalic: true, isUnderline: true).bitmask, 7)
+        XCTAssertEqual(TokenType.keyword.styleFlags, .bold)
+        XCTAssertEqual(TokenType.comment.styleFlags, .italic)
+    }
+
     func testSemanticAliases() {
         let theme = ThemePalette.atomOneDark
         // constant uses number color
@@ -157,4 +282,69 @@ final class ThemePaletteTests: XCTestCase {
         // identifier uses text color
         XCTAssertEqual(theme.hex(for: .identifier), theme.text)
     }
+
+    func testThemePairMatchesFamilyVariants() throws {
+        let pair = ThemePalette.themePair(for: "solarizedLight")
+
+        XCTAssertEqual(pair, ThemePair(light: "solarizedLight", dark: "solarizedDark"))
+        XCTAssertEqual(ThemePalette.themePair(for: "solarizedDark"), pair)
+        XCTAssertEqual(ThemePalette.themePair(for: "solarizedAuto"), pair)
+        let light = try XCTUnwrap(RGBColor(hex: ThemePalette.palette(for: pair.light, systemIsDark: false).background))
+        let dark = try XCTUnwrap(RGBColor(hex: ThemePalette.palette(for: pair.dark, systemIsDark: true).background))
+        XCTAssertFalse(light.isDark)
+        XCTAssertTrue(dark.isDark)
+
+        XCTAssertEqual(ThemePalette.themePair(for: "tokyoNight"), ThemePair(light: "atomOneLight", dark: "tokyoNight"))
+        XCTAssertEqual(ThemePalette.themePair(for: "nope"), ThemePair(light: "atomOneLight", dark: "atomOneDark"))
+    }
+
+    func testScopeOverrideRecolorsOnlyItsToken() throws {
+        let base = ThemePalette.atomOneDark
+        let overrides = [ScopeOverride(scope: "comment.line", foreground: "#ABCDEF", fontStyle: .bold)]
+
+        let palette = try base.applying(overrides)
+
+        XCTAssertEqual(palette.comment, "#ABCDEF")
+        XCTAssertEqual(palette.keyword, base.keyword)
+        XCTAssertEqual(palette.string, base.string)
+        XCTAssertEqual(palette.background, base.background)
+        XCTAssertEqual(ScopeOverride.styleFlags(for: .comment, overrides: overrides), .bold)
+        XCTAssertEqual(ScopeOverride.styleFlags(for: .keyword, overrides: overrides), TokenType.keyword.styleFlags)
+
+        let code = "x // note"
+        let runs = HighlightRuns.paletted(in: code, tokens: [HighlightToken(s: 2, e: 9, c: "comment")], palette: palette)
+        XCTAssertEqual(runs.runs.map { runs.color(of: $0) }, [base.text.uppercased(), "#ABCDEF"])
+    }
+
+    func testScopeOverrideRejectsMalformedColor() {
+        XCTAssertThrowsError(try ThemePalette.atomOneDark.applying([ScopeOverride(scope: "comment", foreground: "#12")])) { error in
+            XCTAssertEqual(error as? ThemePaletteError, .invalidHexColor("#12"))
+        }
+    }
+
+    func testResolvedThemeNamesThePaletteActuallyUsed() {
+        let githubDark = ThemePalette.resolve(appTheme: "githubDark", systemIsDark: false)
+        XCTAssertEqual(githubDark.id, "githubDark")
+        XCTAssertEqual(githubDark.name, "GitHub Dark")
+        XCTAssertEqual(githubDark.background, ThemePalette.githubDark.background)
+        XCTAssertTrue(githubDark.isDark)
+
+        XCTAssertEqual(ThemePalette.resolve(appTheme: "xcodeAuto", systemIsDark: false).id, "xcodeLight")
+        XCTAssertEqual(ThemePalette.resolve(appTheme: "no-such-theme", systemIsDark: true).id, "atomOneDark")
+    }
+
+    func testSortedThemeAndLanguageListsAreAlphabeticalAndStable() {
+        let themes = ThemePalette.sortedThemes()
+        let titles = themes.map { $0.title.lowercased() }
+        XCTAssertEqual(titles, titles.sorted())
+        XCTAssertEqual(themes, ThemePalette.sortedThemes())
+        XCTAssertEqual(Set(themes.map(\.id)), Set(ThemePalette.selectableThemes.map(\.id)))
+
+        let languages = HighlightLanguage.alphabetical.map { $0.displayName.lowercased() }
+        XCTAssertEqual(languages, languages.sorted())
+        XCTAssertEqual(HighlightLanguage.alphabetical.count, HighlightLanguage.all.count)
+        XCTAssertEqual(HighlightLanguage.alphabetical, HighlightLanguage.alphabetical.sorted {
+            ($0.displayName.lowercased(), $0.id) < ($1.displayName.lowercased(), $1.id)
+        })
+    }
 }
@@ -0,0 +1,76 @@
+import XCTest
+@testable import Shared
+
+final class ThreeWayDiffTests: XCTestCase {
+    func testSameLineChangedOnBothSidesIsConflict() {
+        let chunks = ThreeWayDiff.chunks(
+            base: "a\nb\nc\n",
+            local: "a\nlocal b\nc\n",
+            remote: "a\nremote b\nc\n"
+        )
+
+        XCTAssertEqual(chunks, [
+            ThreeWayChunk(status: .unchanged, base: 0..<1, local: 0..<1, remote: 0..<1),
+            ThreeWayChunk(status: .conflict, base: 1..<2, local: 1..<2, remote: 1..<2),
+            ThreeWayChunk(status: .unchanged, base: 2..<3, local: 2..<3, remote: 2..<3),
+        ])
+    }
+
+    func testHighlightedResultMarksConflictingLineOnEverySide() {
+        let base = "a\nlet b\nc\n"
+        let local = "a\nlet b2\nc\n"
+        let remote = "a\nlet b3\nc\n"
+        let keyword = [HighlightToken(s: 2, e: 5, c: "keyword")]
+
+        let result = ThreeWayDiff.highlighted(
+            base: base,
+            baseTokens: keyword,
+            local: local,
+            localTokens: keyword,
+            remote: remote,
+            remoteTokens: keyword
+        )
+
+        for side in [ThreeWaySide.base, .local, .remote] {
+            XCTAssertEqual(result.lineStatuses(of: side), [.unchanged, .conflict, .unchanged])
+        }
+        XCTAssertEqual(result.local.map(\.number), [1, 2, 3])
+        XCTAssertEqual(result.local[1].runs.first, HighlightRun(tokenType: .keyword, text: "let"))
+        XCTAssertEqual(result.remote[1].runs.map(\.text).joined(), "let b3")
+    }
+
+    func testIndependentChangesAreAttributedToEachSide() {
+        let chunks = ThreeWayDiff.chunks(
+            base: "a\nb\nc",
+            local: "A\nb\nc",
+            remote: "a\nb\nc\nd"
+        )
+
+        XCTAssertEqual(chunks.map(\.status), [.localChanged, .unchanged, .remoteChanged])
+        XCTAssertEqual(chunks.last?.remote, 3..<4)
+        XCTAssertEqual(chunks.last?.base, 3..<3)
+    }
+
+    func testIdenticalChangeIsNotAConflict() {
+        let chunks = ThreeWayDiff.chunks(base: "x\ny", local: "x\nz", remote: "x\nz")
+        XCTAssertEqual(chunks.map(\.status), [.unchanged, .bothChanged])
+    }
+
+    func testMatchesPairItemsOfALongestCommonSubsequence() {
+        let matches = ThreeWayDiff.matches(["a", "b", "c", "a", "b", "b", "a"], ["c", "b", "a", "b", "a", "c"])
+        XCTAssertEqual(matches.compactMap { $0 }.count, 4)
+        XCTAssertEqual(matches.compactMap { $0 }, matches.compactMap { $0 }.sorted())
+    }
+
+    func testVeryDifferentInputsFallBackToOneChangedChunk() {
+        let base = (0..<3_000).map { "base \($0)" }.joined(separator: "\n")
+        let local = (0..<3_000).map { "local \($0)" }.joined(separator: "\n")
+
+        let chunks = ThreeWayDiff.chunks(base: "head\n" + base, local: "head\n" + local, remote: "head\n" + base)
+
+        XCTAssertEqual(chunks, [
+            ThreeWayChunk(status: .unchanged, base: 0..<1, local: 0..<1, remote: 0..<1),
+            ThreeWayChunk(status: .localChanged, base: 1..<3_001, local: 1..<3_001, remote: 1..<3_001),
+        ])
+    }
+}
@@ -0,0 +1,14 @@
+import XCTest
+@testable import Shared
+
+final class TimedHighlightTokensTests: XCTestCase {
+    func testElapsedConvertsToMicrosecondsAndCountsTokens() {
+        let tokens = [HighlightToken(s: 0, e: 3, c: "keyword"), HighlightToken(s: 4, e: 5, c: "number")]
+
+        let timed = TimedHighlightTokens(tokens: tokens, elapsed: .milliseconds(2) + .microseconds(5))
+
+        XCTAssertEqual(timed.elapsedMicroseconds, 2_005)
+        XCTAssertEqual(timed.tokenCount, tokens.count)
+        XCTAssertEqual(TimedHighlightTokens(tokens: [], elapsed: .seconds(1)).elapsedMicroseconds, 1_000_000)
+    }
+}